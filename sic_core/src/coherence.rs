@@ -4,7 +4,16 @@
 //! 𝕄ᵢⱼ = Coh(Cᵢ, Cⱼ)    — the Universal Coherence Matrix.
 //! Friction ε truncates weak entanglements to zero.
 
-use crate::context::Context;
+// Index loops mirror the 𝕄ᵢⱼ notation of the formal definitions.
+#![allow(clippy::needless_range_loop)]
+
+pub use crate::config::CoherenceParams;
+use crate::config::{SicConfig, DEFAULT_SYMMETRY_TOLERANCE};
+use crate::context::{Context, Perspective, PerspectiveKind};
+use crate::sparse::SparseCoherenceMatrix;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Instant;
 
 /// Compute coherence between two contexts (Section 9.2).
 ///
@@ -15,39 +24,788 @@ use crate::context::Context;
 ///   - Axiom 5 (Symmetry): d is symmetric → Coh is symmetric
 ///   - Range [0, 1]: exponential of negative value
 pub fn coherence(c1: &Context, c2: &Context) -> f64 {
-    let d = c1.distance(c2);
-    let sigma = 5.0; // characteristic coherence length
-    (-d * d / (sigma * sigma)).exp()
+    coherence_with(c1, c2, &CoherenceParams::default())
+}
+
+/// Coherence under explicit kernel parameters.
+pub fn coherence_with(c1: &Context, c2: &Context, params: &CoherenceParams) -> f64 {
+    coherence_from_distance(c1.distance_with(c2, params.metric), params.sigma)
+}
+
+/// A coherence-valued quantity in [0, 1]: Coh, friction ε, or θ.
+///
+/// Policy: `Coherence::new` and `From<f64>` clamp into [0, 1] (NaN → 0);
+/// `try_new` rejects out-of-range values instead. Bare `f64`s convert
+/// implicitly wherever a signature takes `impl Into<Coherence>`.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Coherence(f64);
+
+impl Coherence {
+    pub const ZERO: Coherence = Coherence(0.0);
+    pub const ONE: Coherence = Coherence(1.0);
+
+    /// Clamp `value` into [0, 1].
+    pub fn new(value: f64) -> Self {
+        if value.is_nan() {
+            Coherence(0.0)
+        } else {
+            Coherence(value.clamp(0.0, 1.0))
+        }
+    }
+
+    /// `Some` only if `value` already lies in [0, 1].
+    pub fn try_new(value: f64) -> Option<Self> {
+        (0.0..=1.0).contains(&value).then_some(Coherence(value))
+    }
+
+    /// Typed Coh(C₁, C₂).
+    pub fn between(c1: &Context, c2: &Context) -> Self {
+        Coherence(coherence(c1, c2))
+    }
+
+    pub fn value(self) -> f64 {
+        self.0
+    }
+
+    /// Contextual friction Φ = 1 − Coh (Section 7.6).
+    pub fn friction(self) -> f64 {
+        1.0 - self.0
+    }
+}
+
+impl From<f64> for Coherence {
+    fn from(value: f64) -> Self {
+        Coherence::new(value)
+    }
+}
+
+impl From<Coherence> for f64 {
+    fn from(coh: Coherence) -> Self {
+        coh.0
+    }
+}
+
+/// Chained coherence Coh₁·Coh₂ — stays in [0, 1].
+impl std::ops::Mul for Coherence {
+    type Output = Coherence;
+
+    fn mul(self, rhs: Coherence) -> Coherence {
+        Coherence(self.0 * rhs.0)
+    }
+}
+
+impl fmt::Display for Coherence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Coherence with an explicit characteristic coherence length σ.
+pub fn coherence_sigma(c1: &Context, c2: &Context, sigma: f64) -> f64 {
+    coherence_from_distance(c1.distance(c2), sigma)
+}
+
+/// The coherence kernel exp(-d²/σ²) applied to a precomputed distance.
+/// A non-positive σ degenerates to the indicator of d = 0. The result is
+/// clamped into [0, 1] like `Coherence::new`, so a NaN distance (from a
+/// non-finite parameter) yields 0 rather than spreading through 𝕄.
+fn coherence_from_distance(d: f64, sigma: f64) -> f64 {
+    if sigma.is_nan() || sigma <= 0.0 {
+        return if d == 0.0 { 1.0 } else { 0.0 };
+    }
+    Coherence::new((-d * d / (sigma * sigma)).exp()).value()
+}
+
+/// Coherence between two perspectives Coh(P₁, P₂) (Section 6.1.1).
+///
+/// Depends only on the perspective kinds: identical kinds are fully
+/// coherent, related kinds partially, and opposed kinds weakly. A compound
+/// scores the share-weighted mean over its constituents.
+/// Reflexive and symmetric like the context coherence.
+pub fn perspective_coherence(p1: &Perspective, p2: &Perspective) -> f64 {
+    kind_coherence(&p1.kind, &p2.kind)
+}
+
+fn kind_coherence(k1: &PerspectiveKind, k2: &PerspectiveKind) -> f64 {
+    use PerspectiveKind::*;
+    if k1 == k2 {
+        return 1.0;
+    }
+    match (k1, k2) {
+        (Compound(parts), other) | (other, Compound(parts)) => parts
+            .iter()
+            .map(|(kind, share)| share * kind_coherence(kind, other))
+            .sum(),
+        (Objective, Deterministic) | (Deterministic, Objective) => 0.8,
+        (Subjective, Intersubjective) | (Intersubjective, Subjective) => 0.7,
+        (Objective, Statistical) | (Statistical, Objective) => 0.6,
+        (Objective, Intersubjective) | (Intersubjective, Objective) => 0.5,
+        (Deterministic, Statistical) | (Statistical, Deterministic) => 0.4,
+        (Objective, Subjective) | (Subjective, Objective) => 0.2,
+        _ => 0.3,
+    }
+}
+
+/// Coherence down-weighted by the age of both contexts (Section 7.5).
+///
+/// Coh_τ(C₁, C₂) = Coh(C₁, C₂) · exp(-age₁/τ) · exp(-age₂/τ)
+///
+/// Unstamped contexts do not age, so this reduces to `coherence`.
+pub fn coherence_aged(c1: &Context, c2: &Context, tau: f64) -> f64 {
+    coherence_aged_at(c1, c2, tau, Instant::now())
+}
+
+/// `coherence_aged` evaluated at an explicit instant (clock injection).
+pub fn coherence_aged_at(c1: &Context, c2: &Context, tau: f64, now: Instant) -> f64 {
+    coherence(c1, c2) * c1.decay_weight_at(tau, now) * c2.decay_weight_at(tau, now)
+}
+
+/// Error decoding a `CoherenceMatrix` from its binary form.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatrixDecodeError {
+    /// Fewer than 8 bytes — the dimension header is missing.
+    MissingHeader,
+    /// The payload length does not match n(n+1)/2 f64 values.
+    LengthMismatch { expected: usize, found: usize },
+}
+
+impl fmt::Display for MatrixDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixDecodeError::MissingHeader => write!(f, "missing matrix dimension header"),
+            MatrixDecodeError::LengthMismatch { expected, found } => write!(
+                f,
+                "expected {} payload bytes, found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MatrixDecodeError {}
+
+/// Which coherence axioms a matrix satisfies (Axioms 4–5 and range).
+///
+/// Each failed check records the first offending (i, j) in row-major order.
+/// Reflexivity, symmetry and range are hard requirements (`is_valid`); the
+/// triangle inequality of the underlying distance is soft, since not every
+/// metric satisfies it, and is only checked by `verify_axioms`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AxiomReport {
+    /// Axiom 4: 𝕄ᵢᵢ = 1
+    pub reflexive: bool,
+    /// Axiom 5: 𝕄ᵢⱼ = 𝕄ⱼᵢ
+    pub symmetric: bool,
+    /// 𝕄ᵢⱼ ∈ [0, 1]
+    pub in_range: bool,
+    pub reflexivity_violation: Option<(usize, usize)>,
+    pub symmetry_violation: Option<(usize, usize)>,
+    pub range_violation: Option<(usize, usize)>,
+    /// First (i, j, k) with d(Cᵢ, Cₖ) > d(Cᵢ, Cⱼ) + d(Cⱼ, Cₖ)
+    pub triangle_violation: Option<(usize, usize, usize)>,
+}
+
+impl AxiomReport {
+    /// True when every hard axiom holds.
+    pub fn is_valid(&self) -> bool {
+        self.reflexive && self.symmetric && self.in_range
+    }
+
+    /// True when a soft check (the triangle inequality) failed.
+    pub fn has_soft_violations(&self) -> bool {
+        self.triangle_violation.is_some()
+    }
+}
+
+/// Check the coherence axioms for a context set under a kernel.
+///
+/// Evaluates `coherence_with` directly for every ordered pair, the
+/// diagonal included — unlike `from_contexts_with`, which fixes 𝕄ᵢᵢ = 1
+/// and mirrors the upper triangle — and checks reflexivity and range
+/// within 1e-12 and symmetry within `DEFAULT_SYMMETRY_TOLERANCE` (hard).
+/// Then checks the triangle inequality of d under `params.metric` with
+/// relative slack 1e-9 (soft; `DistanceMetric::Cosine` can fail it).
+/// O(N³) in the number of contexts.
+pub fn verify_axioms(contexts: &[Context], params: &CoherenceParams) -> AxiomReport {
+    let data: Vec<Vec<f64>> = contexts
+        .iter()
+        .map(|a| contexts.iter().map(|b| coherence_with(a, b, params)).collect())
+        .collect();
+    let mut report = CoherenceMatrix {
+        data,
+        n: contexts.len(),
+        distances: None,
+    }
+    .axiom_report(1e-12, DEFAULT_SYMMETRY_TOLERANCE);
+
+    let n = contexts.len();
+    let d: Vec<Vec<f64>> = contexts
+        .iter()
+        .map(|a| contexts.iter().map(|b| a.distance_with(b, params.metric)).collect())
+        .collect();
+    report.triangle_violation = (0..n)
+        .flat_map(|i| (0..n).flat_map(move |j| (0..n).map(move |k| (i, j, k))))
+        .find(|&(i, j, k)| {
+            let detour = d[i][j] + d[j][k];
+            d[i][k] > detour + 1e-9 * detour.max(1.0)
+        });
+    report
+}
+
+/// Power iteration did not reach the requested tolerance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotConverged {
+    /// The estimate reached when the iteration budget ran out
+    pub estimate: f64,
+    /// Eigen-residual ‖𝕄v − λv‖ at the last iteration
+    pub residual: f64,
+    pub iterations: usize,
+}
+
+impl fmt::Display for NotConverged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "power iteration did not converge after {} iterations (residual {:e})",
+            self.iterations, self.residual
+        )
+    }
+}
+
+impl std::error::Error for NotConverged {}
+
+/// Internal state of a finished power iteration.
+pub(crate) struct PowerIteration {
+    pub(crate) lambda: f64,
+    iterations: usize,
+    residual: f64,
+    converged: bool,
+    vector: Vec<f64>,
+}
+
+/// The one stopping rule of every power iteration here: λ moved by less
+/// than `tol` and the eigen-residual is below √tol.
+pub(crate) fn settled(tol: f64) -> impl Fn(f64, f64) -> bool {
+    let residual_tol = tol.sqrt();
+    move |delta, residual| delta < tol && residual < residual_tol
+}
+
+/// Power iteration of the n × n operator `multiply`, stopping once
+/// `done(|Δλ|, residual)` holds, where residual = ‖𝕄v − λv‖. `converged`
+/// records whether it did.
+pub(crate) fn power_iterate(
+    n: usize,
+    max_iter: usize,
+    multiply: impl Fn(&[f64]) -> Vec<f64>,
+    done: impl Fn(f64, f64) -> bool,
+    mut observe: impl FnMut(f64, &[f64]),
+) -> PowerIteration {
+    let mut v = vec![1.0 / (n as f64).sqrt(); n];
+    let mut lambda = 0.0;
+    let mut residual = f64::INFINITY;
+    let mut iterations = 0;
+    let mut converged = false;
+
+    while iterations < max_iter {
+        iterations += 1;
+
+        let mut w = multiply(&v);
+
+        // Compute eigenvalue estimate
+        let previous = lambda;
+        lambda = 0.0;
+        for i in 0..n {
+            lambda += w[i] * v[i];
+        }
+
+        // Eigen-residual ‖𝕄v − λv‖
+        residual = w
+            .iter()
+            .zip(v.iter())
+            .map(|(wi, vi)| (wi - lambda * vi).powi(2))
+            .sum::<f64>()
+            .sqrt();
+
+        // Normalize
+        let norm: f64 = w.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            for x in &mut w {
+                *x /= norm;
+            }
+        }
+        v = w;
+        observe(lambda, &v);
+
+        if done((lambda - previous).abs(), residual) {
+            converged = true;
+            break;
+        }
+    }
+
+    PowerIteration {
+        lambda,
+        iterations,
+        residual,
+        converged,
+        vector: v,
+    }
+}
+
+/// The dominant mode of 𝕄 from `global_coherence_detailed`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoherenceSpectrum {
+    /// Γ = λ_max / N
+    pub gamma: f64,
+    pub lambda_max: f64,
+    /// Unit-norm dominant eigenvector; its large entries are the contexts
+    /// that dominate the leading mode
+    pub eigenvector: Vec<f64>,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+/// Result of the Sections 11.4–11.6 collapse workflow.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalysisReport {
+    /// Γ of the unthresholded matrix
+    pub gamma_before: f64,
+    /// Friction ε applied
+    pub epsilon: f64,
+    /// Γ after friction
+    pub gamma_after: f64,
+    /// Member indices of each cluster, in label order
+    pub clusters: Vec<Vec<usize>>,
+    /// γ_k per cluster
+    pub cluster_gammas: Vec<f64>,
+    /// Collapse verdict per cluster (γ_k > θ)
+    pub collapsed: Vec<bool>,
+}
+
+/// Group entity indices by cluster label: result[k] lists members of cluster k.
+pub fn cluster_groups(labels: &[usize]) -> Vec<Vec<usize>> {
+    let n_clusters = labels.iter().copied().max().map(|m| m + 1).unwrap_or(0);
+    let mut groups = vec![Vec::new(); n_clusters];
+    for (i, &label) in labels.iter().enumerate() {
+        groups[label].push(i);
+    }
+    groups
+}
+
+/// Renumber cluster labels 0, 1, 2, … in ascending order of each
+/// cluster's smallest member index — the canonical labelling.
+pub fn relabel_by_min_index(labels: &[usize]) -> Vec<usize> {
+    // Scanning indices in order meets each cluster first at its minimum
+    let mut remap = HashMap::new();
+    labels
+        .iter()
+        .map(|l| {
+            let next = remap.len();
+            *remap.entry(*l).or_insert(next)
+        })
+        .collect()
+}
+
+/// Builds 𝕄 from contexts supplied one at a time.
+///
+/// Coherence is pairwise, so every added context is retained to score
+/// the ones that follow: memory is O(N) contexts plus the O(N²) matrix,
+/// the same as `from_contexts`. What streaming saves is the caller's
+/// need to collect the contexts into a slice first.
+#[derive(Debug, Clone)]
+pub struct MatrixBuilder {
+    params: CoherenceParams,
+    contexts: Vec<Context>,
+    data: Vec<Vec<f64>>,
+}
+
+impl Default for MatrixBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MatrixBuilder {
+    pub fn new() -> Self {
+        Self::with_config(&SicConfig::default())
+    }
+
+    /// Use the coherence kernel from `config`.
+    pub fn with_config(config: &SicConfig) -> Self {
+        Self {
+            params: config.coherence,
+            contexts: Vec::new(),
+            data: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.contexts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contexts.is_empty()
+    }
+
+    /// Append a context, filling its row and column of 𝕄.
+    pub fn add(&mut self, context: Context) {
+        let row: Vec<f64> = self
+            .contexts
+            .iter()
+            .map(|c| coherence_with(c, &context, &self.params))
+            .collect();
+        for (existing, &coh) in self.data.iter_mut().zip(row.iter()) {
+            existing.push(coh); // Axiom 5: symmetry
+        }
+        let mut new_row = row;
+        new_row.push(1.0); // Axiom 4: reflexivity
+        self.data.push(new_row);
+        self.contexts.push(context);
+    }
+
+    /// Finish into a `CoherenceMatrix`.
+    pub fn build(self) -> CoherenceMatrix {
+        CoherenceMatrix {
+            n: self.data.len(),
+            data: self.data,
+            distances: None,
+        }
+    }
+}
+
+impl Extend<Context> for MatrixBuilder {
+    fn extend<I: IntoIterator<Item = Context>>(&mut self, iter: I) {
+        for context in iter {
+            self.add(context);
+        }
+    }
 }
 
 /// The Universal Coherence Matrix 𝕄 (Section 11.1).
 ///
 /// A symmetric N×N matrix where 𝕄ᵢⱼ = Coh(Cᵢ, Cⱼ).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CoherenceMatrix {
     pub data: Vec<Vec<f64>>,
     pub n: usize,
+    /// Raw contextual distances d(Cᵢ, Cⱼ), when retained at build time.
+    pub distances: Option<Vec<Vec<f64>>>,
 }
 
 impl CoherenceMatrix {
     /// Build 𝕄 from a set of contexts.
     pub fn from_contexts(contexts: &[Context]) -> Self {
+        Self::from_contexts_config(contexts, &SicConfig::default())
+    }
+
+    /// Build 𝕄 using the coherence kernel from `config`.
+    pub fn from_contexts_config(contexts: &[Context], config: &SicConfig) -> Self {
+        Self::from_contexts_with(contexts, &config.coherence)
+    }
+
+    /// Build 𝕄 with the same kernel parameters in every cell.
+    pub fn from_contexts_with(contexts: &[Context], params: &CoherenceParams) -> Self {
+        let n = contexts.len();
+        let mut data = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            data[i][i] = 1.0; // Axiom 4: reflexivity
+            for j in (i + 1)..n {
+                let coh = coherence_with(&contexts[i], &contexts[j], params);
+                data[i][j] = coh;
+                data[j][i] = coh; // Axiom 5: symmetry
+            }
+        }
+        Self {
+            data,
+            n,
+            distances: None,
+        }
+    }
+
+    /// `from_contexts` with the upper triangle computed across threads.
+    /// Coherence is a pure function, so the result is bit-identical to the
+    /// serial build.
+    #[cfg(feature = "rayon")]
+    pub fn from_contexts_parallel(contexts: &[Context]) -> Self {
+        Self::from_contexts_parallel_with(contexts, &CoherenceParams::default())
+    }
+
+    /// `from_contexts_with` with the upper triangle computed across threads.
+    #[cfg(feature = "rayon")]
+    pub fn from_contexts_parallel_with(contexts: &[Context], params: &CoherenceParams) -> Self {
+        use rayon::prelude::*;
+        let n = contexts.len();
+        let upper: Vec<Vec<f64>> = (0..n)
+            .into_par_iter()
+            .map(|i| {
+                contexts[i + 1..]
+                    .iter()
+                    .map(|other| coherence_with(&contexts[i], other, params))
+                    .collect()
+            })
+            .collect();
+        let mut data = vec![vec![0.0; n]; n];
+        for (i, row) in upper.into_iter().enumerate() {
+            data[i][i] = 1.0; // Axiom 4: reflexivity
+            for (offset, coh) in row.into_iter().enumerate() {
+                let j = i + 1 + offset;
+                data[i][j] = coh;
+                data[j][i] = coh; // Axiom 5: symmetry
+            }
+        }
+        Self {
+            data,
+            n,
+            distances: None,
+        }
+    }
+
+    /// Build 𝕄 and also retain the distance matrix d(Cᵢ, Cⱼ) computed in the
+    /// same pass, for distance-based analyses (e.g. MDS embedding). Both
+    /// use the kernel from `config`, so d is measured in its metric.
+    pub fn from_contexts_with_distances(contexts: &[Context], config: &SicConfig) -> Self {
+        let params = &config.coherence;
         let n = contexts.len();
         let mut data = vec![vec![0.0; n]; n];
+        let mut distances = vec![vec![0.0; n]; n];
         for i in 0..n {
             data[i][i] = 1.0; // Axiom 4: reflexivity
             for j in (i + 1)..n {
-                let coh = coherence(&contexts[i], &contexts[j]);
+                let d = contexts[i].distance_with(&contexts[j], params.metric);
+                let coh = coherence_from_distance(d, params.sigma);
                 data[i][j] = coh;
                 data[j][i] = coh; // Axiom 5: symmetry
+                distances[i][j] = d;
+                distances[j][i] = d;
+            }
+        }
+        Self {
+            data,
+            n,
+            distances: Some(distances),
+        }
+    }
+
+    /// Append a context, computing only its N new coherences.
+    ///
+    /// `existing` must be the contexts of the current rows, in order. The
+    /// new row and column are filled with Coh(existing[j], ctx) and the
+    /// diagonal with 1; retained distances grow alongside. Returns the
+    /// new context's index.
+    ///
+    /// Panics if `existing.len() != self.n`.
+    pub fn push_context(&mut self, ctx: &Context, existing: &[Context]) -> usize {
+        self.push_context_with(ctx, existing, &CoherenceParams::default())
+    }
+
+    /// `push_context` under an explicit kernel.
+    pub fn push_context_with(
+        &mut self,
+        ctx: &Context,
+        existing: &[Context],
+        params: &CoherenceParams,
+    ) -> usize {
+        assert_eq!(existing.len(), self.n, "existing contexts must match the matrix size");
+        let distances: Vec<f64> = existing
+            .iter()
+            .map(|other| other.distance_with(ctx, params.metric))
+            .collect();
+        let mut row: Vec<f64> = distances
+            .iter()
+            .map(|&d| coherence_from_distance(d, params.sigma))
+            .collect();
+        for (data_row, &coh) in self.data.iter_mut().zip(row.iter()) {
+            data_row.push(coh); // Axiom 5: symmetry
+        }
+        row.push(1.0); // Axiom 4: reflexivity
+        self.data.push(row);
+
+        if let Some(matrix) = self.distances.as_mut() {
+            for (distance_row, &d) in matrix.iter_mut().zip(distances.iter()) {
+                distance_row.push(d);
+            }
+            let mut row = distances;
+            row.push(0.0);
+            matrix.push(row);
+        }
+        self.n += 1;
+        self.n - 1
+    }
+
+    /// Drop context `i`: its row and column of 𝕄 (and of the retained
+    /// distances) are removed and later indices shift down by one. The
+    /// remaining entries — including zeros left by friction — are kept
+    /// as they are, so cluster labels only change where `i` was a bridge.
+    ///
+    /// Panics if `i >= self.n`.
+    pub fn remove_context(&mut self, i: usize) {
+        assert!(i < self.n, "context index {i} out of range for {} contexts", self.n);
+        self.data.remove(i);
+        for row in &mut self.data {
+            row.remove(i);
+        }
+        if let Some(matrix) = self.distances.as_mut() {
+            matrix.remove(i);
+            for row in matrix.iter_mut() {
+                row.remove(i);
+            }
+        }
+        self.n -= 1;
+    }
+
+    /// k-nearest-neighbour coherence graph, without a dense 𝕄.
+    ///
+    /// Each context keeps edges to its k most coherent others (ties broken
+    /// by lower index); the graph is then symmetrized by union, so every
+    /// node has at least min(k, N − 1) edges.
+    pub fn knn_graph(contexts: &[Context], k: usize) -> SparseCoherenceMatrix {
+        Self::knn_graph_with(contexts, k, &CoherenceParams::default())
+    }
+
+    /// `knn_graph` under explicit kernel parameters, matching the 𝕄 of
+    /// `from_contexts_with`.
+    pub fn knn_graph_with(
+        contexts: &[Context],
+        k: usize,
+        params: &CoherenceParams,
+    ) -> SparseCoherenceMatrix {
+        let n = contexts.len();
+        let mut rows: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
+        for i in 0..n {
+            let mut candidates: Vec<(usize, f64)> = (0..n)
+                .filter(|&j| j != i)
+                .map(|j| (j, coherence_with(&contexts[i], &contexts[j], params)))
+                .collect();
+            candidates.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+            for &(j, coh) in candidates.iter().take(k) {
+                rows[i].push((j, coh));
+                rows[j].push((i, coh));
+            }
+        }
+        SparseCoherenceMatrix::from_adjacency(rows)
+    }
+
+    /// The retained distance matrix, if built with `from_contexts_with_distances`.
+    pub fn distance_matrix(&self) -> Option<&Vec<Vec<f64>>> {
+        self.distances.as_ref()
+    }
+
+    /// Classical multidimensional scaling of the contexts into 2D.
+    ///
+    /// Double-centers the squared retained distances, B = -½·J·D²·J, and
+    /// uses the top two eigenpairs of B as coordinates (vₖ·√λₖ). Returns
+    /// `None` unless 𝕄 was built with `from_contexts_with_distances`.
+    pub fn mds_2d(&self) -> Option<Vec<(f64, f64)>> {
+        let d = self.distances.as_ref()?;
+        let n = self.n;
+        if n == 0 {
+            return Some(Vec::new());
+        }
+
+        let sq: Vec<Vec<f64>> = d.iter().map(|row| row.iter().map(|x| x * x).collect()).collect();
+        let row_means: Vec<f64> = sq.iter().map(|row| row.iter().sum::<f64>() / n as f64).collect();
+        let grand_mean = row_means.iter().sum::<f64>() / n as f64;
+        let mut b = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            for j in 0..n {
+                b[i][j] = -0.5 * (sq[i][j] - row_means[i] - row_means[j] + grand_mean);
+            }
+        }
+
+        let (eigenvalues, eigenvectors) = jacobi_eigen(&b);
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| eigenvalues[b].total_cmp(&eigenvalues[a]));
+        let axis = |k: usize| -> Vec<f64> {
+            match order.get(k) {
+                Some(&idx) => {
+                    let scale = eigenvalues[idx].max(0.0).sqrt();
+                    eigenvectors[idx].iter().map(|x| x * scale).collect()
+                }
+                None => vec![0.0; n],
+            }
+        };
+        let (x, y) = (axis(0), axis(1));
+        Some(x.into_iter().zip(y).collect())
+    }
+
+    /// Check the coherence axioms: reflexivity and range within `tol`,
+    /// symmetry within `symmetry_tol`. Pass `DEFAULT_SYMMETRY_TOLERANCE`
+    /// (1e-12) to accept float round-trip noise, or 0 to demand exactness.
+    pub fn axiom_report(&self, tol: f64, symmetry_tol: f64) -> AxiomReport {
+        let mut reflexivity_violation = None;
+        let mut symmetry_violation = None;
+        let mut range_violation = None;
+
+        for i in 0..self.n {
+            if reflexivity_violation.is_none() && (self.data[i][i] - 1.0).abs() > tol {
+                reflexivity_violation = Some((i, i));
+            }
+            for j in 0..self.n {
+                let v = self.data[i][j];
+                if range_violation.is_none() && !(-tol..=1.0 + tol).contains(&v) {
+                    range_violation = Some((i, j));
+                }
+                if symmetry_violation.is_none()
+                    && j > i
+                    && (v - self.data[j][i]).abs() > symmetry_tol
+                {
+                    symmetry_violation = Some((i, j));
+                }
+            }
+        }
+
+        AxiomReport {
+            reflexive: reflexivity_violation.is_none(),
+            symmetric: symmetry_violation.is_none(),
+            in_range: range_violation.is_none(),
+            reflexivity_violation,
+            symmetry_violation,
+            range_violation,
+            triangle_violation: None,
+        }
+    }
+
+    /// `axiom_report` at the default tolerances (exact reflexivity and
+    /// range, symmetry within `DEFAULT_SYMMETRY_TOLERANCE`).
+    pub fn is_valid(&self) -> bool {
+        self.axiom_report(0.0, DEFAULT_SYMMETRY_TOLERANCE).is_valid()
+    }
+
+    /// 𝕄ᵢⱼ read from the upper triangle, so traversals see one value per
+    /// pair even when 𝕄 is asymmetric at the round-off level.
+    fn link(&self, i: usize, j: usize) -> f64 {
+        if i <= j {
+            self.data[i][j]
+        } else {
+            self.data[j][i]
+        }
+    }
+
+    /// Restore Axioms 4–5 on a slightly asymmetric matrix (e.g. loaded
+    /// from noisy data): 𝕄ᵢⱼ, 𝕄ⱼᵢ ← (𝕄ᵢⱼ + 𝕄ⱼᵢ) / 2 and 𝕄ᵢᵢ ← 1.
+    /// A precondition for the symmetric eigen routines.
+    pub fn symmetrize(&mut self) {
+        for i in 0..self.n {
+            self.data[i][i] = 1.0;
+            for j in (i + 1)..self.n {
+                let mean = (self.data[i][j] + self.data[j][i]) / 2.0;
+                self.data[i][j] = mean;
+                self.data[j][i] = mean;
             }
         }
-        Self { data, n }
+    }
+
+    /// Non-mutating form of `symmetrize`.
+    pub fn symmetrized(&self) -> CoherenceMatrix {
+        let mut m = self.clone();
+        m.symmetrize();
+        m
     }
 
     /// Apply entanglement friction ε (Section 11.4).
     /// Truncates coherences below ε to zero, producing a sparse matrix.
-    pub fn apply_friction(&mut self, epsilon: f64) {
+    pub fn apply_friction(&mut self, epsilon: impl Into<Coherence>) {
+        let epsilon = epsilon.into().value();
         for i in 0..self.n {
             for j in 0..self.n {
                 if i != j && self.data[i][j] < epsilon {
@@ -57,10 +815,47 @@ impl CoherenceMatrix {
         }
     }
 
+    /// Fraction of edges that would survive `apply_friction(ε)`, for each ε.
+    ///
+    /// Returns (ε, surviving / N(N−1)/2) without mutating 𝕄; an edge is an
+    /// upper-triangle entry with 𝕄ᵢⱼ > 0. Non-increasing in ε.
+    pub fn sparsity_curve(&self, epsilons: &[f64]) -> Vec<(f64, f64)> {
+        let mut weights = Vec::with_capacity(self.n * self.n.saturating_sub(1) / 2);
+        for i in 0..self.n {
+            for j in (i + 1)..self.n {
+                weights.push(self.data[i][j]);
+            }
+        }
+        let pairs = weights.len();
+        epsilons
+            .iter()
+            .map(|&epsilon| {
+                if pairs == 0 {
+                    return (epsilon, 0.0);
+                }
+                let surviving = weights.iter().filter(|&&w| w > 0.0 && w >= epsilon).count();
+                (epsilon, surviving as f64 / pairs as f64)
+            })
+            .collect()
+    }
+
     /// Find connected clusters via BFS (Section 11.4 - Cluster Decomposition).
     ///
     /// Returns a vector where labels[i] = cluster index for entity i.
+    /// Clusters are numbered in ascending order of their smallest member.
+    /// The `> 0` case of `clusters_at`: every surviving link connects.
     pub fn find_clusters(&self) -> Vec<usize> {
+        // clusters_at never follows a zero link, so threshold 0 is exactly > 0
+        self.clusters_at(0.0)
+    }
+
+    /// Find clusters at a given link strength without mutating 𝕄.
+    ///
+    /// Equivalent to cloning, applying friction ε = `threshold`, and calling
+    /// `find_clusters` — positive edges with 𝕄ᵢⱼ ≥ threshold are
+    /// connections, regardless of any friction already applied. Labels
+    /// follow the smallest-member ordering; singletons keep their own.
+    pub fn clusters_at(&self, threshold: f64) -> Vec<usize> {
         let mut labels = vec![usize::MAX; self.n];
         let mut current_cluster = 0;
 
@@ -68,13 +863,13 @@ impl CoherenceMatrix {
             if labels[start] != usize::MAX {
                 continue;
             }
-            // BFS from this node
             let mut queue = vec![start];
             labels[start] = current_cluster;
 
             while let Some(node) = queue.pop() {
                 for neighbor in 0..self.n {
-                    if labels[neighbor] == usize::MAX && self.data[node][neighbor] > 0.0 {
+                    let w = self.link(node, neighbor);
+                    if labels[neighbor] == usize::MAX && w > 0.0 && w >= threshold {
                         labels[neighbor] = current_cluster;
                         queue.push(neighbor);
                     }
@@ -83,62 +878,462 @@ impl CoherenceMatrix {
             current_cluster += 1;
         }
 
-        labels
+        relabel_by_min_index(&labels)
     }
 
-    /// Count the number of distinct clusters.
-    pub fn num_clusters(&self) -> usize {
-        let labels = self.find_clusters();
-        labels.iter().copied().max().map(|m| m + 1).unwrap_or(0)
+    /// Alias of `clusters_at`.
+    pub fn find_clusters_threshold(&self, link_threshold: f64) -> Vec<usize> {
+        self.clusters_at(link_threshold)
     }
 
-    /// Compute global coherence Γ = λ_max / N (Section 11.6).
+    /// Single-linkage hierarchy of the clusters as the link threshold
+    /// sweeps from 1 down to 0.
     ///
-    /// Uses power iteration to approximate the dominant eigenvalue.
-    pub fn global_coherence(&self) -> f64 {
-        if self.n == 0 {
-            return 0.0;
-        }
-
-        // Power iteration for dominant eigenvalue
-        let mut v = vec![1.0 / (self.n as f64).sqrt(); self.n];
-        let mut lambda = 0.0;
-
-        for _ in 0..100 {
-            // Matrix-vector multiply
-            let mut w = vec![0.0; self.n];
-            for i in 0..self.n {
-                for j in 0..self.n {
-                    w[i] += self.data[i][j] * v[j];
+    /// Positive links are visited strongest first (ties by ascending
+    /// (i, j)); each one joining two clusters records a `Merge`.
+    /// `Dendrogram::cut(t)` then equals `clusters_at(t)` for any t.
+    pub fn cluster_dendrogram(&self) -> Dendrogram {
+        let mut links = Vec::new();
+        for i in 0..self.n {
+            for j in (i + 1)..self.n {
+                let w = self.link(i, j);
+                if w > 0.0 {
+                    links.push((w, i, j));
                 }
             }
+        }
+        links.sort_by(|a, b| b.0.total_cmp(&a.0).then((a.1, a.2).cmp(&(b.1, b.2))));
 
-            // Compute eigenvalue estimate
-            lambda = 0.0;
-            for i in 0..self.n {
-                lambda += w[i] * v[i];
-            }
-
-            // Normalize
-            let norm: f64 = w.iter().map(|x| x * x).sum::<f64>().sqrt();
-            if norm > 0.0 {
-                for x in &mut w {
-                    *x /= norm;
-                }
+        let mut sets = IncrementalClusterer::new(0.0);
+        for _ in 0..self.n {
+            sets.insert(&[]);
+        }
+        // Smallest member and size of each set, indexed by its root
+        let mut smallest: Vec<usize> = (0..self.n).collect();
+        let mut size = vec![1; self.n];
+        let mut merges = Vec::new();
+        for (w, i, j) in links {
+            let (ri, rj) = (sets.find(i), sets.find(j));
+            if ri == rj {
+                continue;
             }
-            v = w;
+            let (left, right) = (smallest[ri].min(smallest[rj]), smallest[ri].max(smallest[rj]));
+            let merged_size = size[ri] + size[rj];
+            sets.union(ri, rj);
+            let root = sets.find(i);
+            smallest[root] = left;
+            size[root] = merged_size;
+            merges.push(Merge {
+                threshold: w,
+                left,
+                right,
+                size: merged_size,
+            });
         }
 
-        lambda / self.n as f64
+        Dendrogram { n: self.n, merges }
     }
 
-    /// Local collapse analysis for a specific cluster (Section 11.5).
+    /// Absorb singleton clusters into their most coherent neighbor's cluster.
     ///
-    /// Returns (gamma_k, collapsed) where gamma_k = λ₁⁽ᵏ⁾ / Tr(𝕄ₖ).
-    pub fn local_collapse(&self, cluster_indices: &[usize], theta: f64) -> (f64, bool) {
-        let n_k = cluster_indices.len();
-        if n_k <= 1 {
-            return (1.0, true);
+    /// Policy: each singleton i joins the cluster of the non-singleton node j
+    /// maximizing 𝕄ᵢⱼ, even if that edge fell below the friction ε used to
+    /// build `labels`, as long as 𝕄ᵢⱼ ≥ `min_coh`. Singletons whose best
+    /// coherence is below `min_coh` are truly disconnected and stay alone.
+    /// Singletons never absorb each other, so the result is order-independent.
+    ///
+    /// Run this on the matrix *before* friction (e.g. with labels from
+    /// `clusters_at`) — friction zeroes the weak edges it needs to see.
+    /// Labels are renumbered contiguously in order of first appearance.
+    pub fn absorb_singletons(&self, labels: &[usize], min_coh: f64) -> Vec<usize> {
+        let mut sizes = HashMap::new();
+        for &l in labels {
+            *sizes.entry(l).or_insert(0usize) += 1;
+        }
+
+        let mut absorbed = labels.to_vec();
+        for i in 0..self.n {
+            if sizes[&labels[i]] != 1 {
+                continue;
+            }
+            let best = (0..self.n)
+                .filter(|&j| j != i && sizes[&labels[j]] > 1)
+                .max_by(|&a, &b| self.data[i][a].total_cmp(&self.data[i][b]));
+            if let Some(j) = best {
+                if self.data[i][j] >= min_coh {
+                    absorbed[i] = labels[j];
+                }
+            }
+        }
+
+        relabel_by_min_index(&absorbed)
+    }
+
+    /// Mean within-cluster coherence of each cluster, indexed by label.
+    ///
+    /// Averages 𝕄ᵢⱼ over distinct pairs sharing a label; a singleton
+    /// cluster has no pairs and scores 1 (Axiom 4).
+    pub fn cluster_cohesion(&self, labels: &[usize]) -> Vec<f64> {
+        cluster_groups(labels)
+            .iter()
+            .map(|members| {
+                let mut sum = 0.0;
+                let mut pairs = 0usize;
+                for (a, &i) in members.iter().enumerate() {
+                    for &j in &members[a + 1..] {
+                        sum += self.link(i, j);
+                        pairs += 1;
+                    }
+                }
+                if pairs == 0 { 1.0 } else { sum / pairs as f64 }
+            })
+            .collect()
+    }
+
+    /// Mean coherence across pairs with different labels (0 if none).
+    pub fn cluster_separation(&self, labels: &[usize]) -> f64 {
+        let mut sum = 0.0;
+        let mut pairs = 0usize;
+        for i in 0..self.n {
+            for j in (i + 1)..self.n {
+                if labels[i] != labels[j] {
+                    sum += self.link(i, j);
+                    pairs += 1;
+                }
+            }
+        }
+        if pairs == 0 { 0.0 } else { sum / pairs as f64 }
+    }
+
+    /// Mean silhouette of a partition, with coherence as the similarity.
+    ///
+    /// For context i, a = mean Coh to its own cluster and b = the highest
+    /// mean Coh to any other cluster; s(i) = (a − b) / max(a, b). Singletons
+    /// score 0. Ranges over [−1, 1]; higher is a better partition.
+    pub fn silhouette(&self, labels: &[usize]) -> f64 {
+        if self.n == 0 {
+            return 0.0;
+        }
+        let groups = cluster_groups(labels);
+        let mean_to = |i: usize, members: &[usize]| -> Option<f64> {
+            let others: Vec<usize> = members.iter().copied().filter(|&j| j != i).collect();
+            if others.is_empty() {
+                return None;
+            }
+            Some(others.iter().map(|&j| self.link(i, j)).sum::<f64>() / others.len() as f64)
+        };
+
+        let total: f64 = (0..self.n)
+            .map(|i| {
+                let Some(a) = mean_to(i, &groups[labels[i]]) else {
+                    return 0.0;
+                };
+                let b = groups
+                    .iter()
+                    .enumerate()
+                    .filter(|(label, members)| *label != labels[i] && !members.is_empty())
+                    .filter_map(|(_, members)| mean_to(i, members))
+                    .fold(f64::NEG_INFINITY, f64::max);
+                if b == f64::NEG_INFINITY {
+                    return 0.0;
+                }
+                let scale = a.max(b);
+                if scale > 0.0 { (a - b) / scale } else { 0.0 }
+            })
+            .sum();
+        total / self.n as f64
+    }
+
+    /// Row-normalized 𝕄: Pᵢⱼ = 𝕄ᵢⱼ / Σₖ 𝕄ᵢₖ, a random-walk transition
+    /// matrix. A row summing to zero becomes uniform (1/N).
+    pub fn to_stochastic(&self) -> Vec<Vec<f64>> {
+        self.data
+            .iter()
+            .map(|row| {
+                let sum: f64 = row.iter().sum();
+                if sum > 0.0 {
+                    row.iter().map(|v| v / sum).collect()
+                } else {
+                    vec![1.0 / self.n as f64; self.n]
+                }
+            })
+            .collect()
+    }
+
+    /// Context importance by PageRank on `to_stochastic`.
+    ///
+    /// Runs `iters` power-method steps of r ← (1 − d)/N + d·Pᵀr from the
+    /// uniform vector, with damping d = `damping`. Scores sum to 1.
+    pub fn pagerank(&self, damping: f64, iters: usize) -> Vec<f64> {
+        let n = self.n;
+        if n == 0 {
+            return Vec::new();
+        }
+        let p = self.to_stochastic();
+        let mut rank = vec![1.0 / n as f64; n];
+        for _ in 0..iters {
+            let mut next = vec![(1.0 - damping) / n as f64; n];
+            for i in 0..n {
+                for j in 0..n {
+                    next[j] += damping * rank[i] * p[i][j];
+                }
+            }
+            rank = next;
+        }
+        rank
+    }
+
+    /// Betweenness centrality of each context (Brandes' algorithm).
+    ///
+    /// Runs on the unweighted graph whose edges are pairs with
+    /// 𝕄ᵢⱼ ≥ `min_coh`. A high score marks a bridge context: removing it
+    /// fragments the coherence graph the most. Scores count each unordered
+    /// pair of endpoints once.
+    pub fn betweenness(&self, min_coh: f64) -> Vec<f64> {
+        let n = self.n;
+        let adjacent =
+            |i: usize, j: usize| i != j && self.data[i][j] > 0.0 && self.data[i][j] >= min_coh;
+        let mut centrality = vec![0.0; n];
+
+        for s in 0..n {
+            let mut stack = Vec::with_capacity(n);
+            let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+            let mut sigma = vec![0.0; n];
+            let mut dist = vec![-1i64; n];
+            sigma[s] = 1.0;
+            dist[s] = 0;
+
+            let mut queue = std::collections::VecDeque::from([s]);
+            while let Some(v) = queue.pop_front() {
+                stack.push(v);
+                for w in 0..n {
+                    if !adjacent(v, w) {
+                        continue;
+                    }
+                    if dist[w] < 0 {
+                        dist[w] = dist[v] + 1;
+                        queue.push_back(w);
+                    }
+                    if dist[w] == dist[v] + 1 {
+                        sigma[w] += sigma[v];
+                        predecessors[w].push(v);
+                    }
+                }
+            }
+
+            let mut delta = vec![0.0; n];
+            while let Some(w) = stack.pop() {
+                for &v in &predecessors[w] {
+                    delta[v] += sigma[v] / sigma[w] * (1.0 + delta[w]);
+                }
+                if w != s {
+                    centrality[w] += delta[w];
+                }
+            }
+        }
+
+        // Undirected graph: every pair was counted from both endpoints
+        for c in &mut centrality {
+            *c /= 2.0;
+        }
+        centrality
+    }
+
+    /// Single-linkage clustering into `k` groups (maximum spanning tree cut).
+    ///
+    /// Builds the maximum spanning tree of 𝕄 (Prim), removes its k−1
+    /// weakest edges, and labels the resulting components in order of their
+    /// smallest member, like `find_clusters`. Deterministic and free of any
+    /// threshold; `k` is clamped to [1, N].
+    pub fn single_linkage(&self, k: usize) -> Vec<usize> {
+        let n = self.n;
+        if n == 0 {
+            return Vec::new();
+        }
+        let k = k.clamp(1, n);
+
+        // Prim's algorithm, maximizing coherence
+        let mut in_tree = vec![false; n];
+        let mut best = vec![f64::NEG_INFINITY; n];
+        let mut parent = vec![usize::MAX; n];
+        let mut edges: Vec<(f64, usize, usize)> = Vec::with_capacity(n - 1);
+        best[0] = 0.0;
+        for _ in 0..n {
+            let u = (0..n)
+                .filter(|&v| !in_tree[v])
+                .max_by(|&a, &b| best[a].total_cmp(&best[b]).then(b.cmp(&a)))
+                .unwrap();
+            in_tree[u] = true;
+            if parent[u] != usize::MAX {
+                edges.push((self.data[parent[u]][u], parent[u], u));
+            }
+            for v in 0..n {
+                if !in_tree[v] && self.data[u][v] > best[v] {
+                    best[v] = self.data[u][v];
+                    parent[v] = u;
+                }
+            }
+        }
+
+        // Keep all but the k−1 weakest tree edges
+        edges.sort_by(|a, b| b.0.total_cmp(&a.0));
+        edges.truncate(n - k);
+
+        let mut adjacency = vec![Vec::new(); n];
+        for &(_, a, b) in &edges {
+            adjacency[a].push(b);
+            adjacency[b].push(a);
+        }
+        let mut labels = vec![usize::MAX; n];
+        let mut current = 0;
+        for start in 0..n {
+            if labels[start] != usize::MAX {
+                continue;
+            }
+            let mut stack = vec![start];
+            labels[start] = current;
+            while let Some(node) = stack.pop() {
+                for &next in &adjacency[node] {
+                    if labels[next] == usize::MAX {
+                        labels[next] = current;
+                        stack.push(next);
+                    }
+                }
+            }
+            current += 1;
+        }
+        labels
+    }
+
+    /// Count the number of distinct clusters.
+    pub fn num_clusters(&self) -> usize {
+        let labels = self.find_clusters();
+        labels.iter().copied().max().map(|m| m + 1).unwrap_or(0)
+    }
+
+    /// Compute global coherence Γ = λ_max / N (Section 11.6).
+    ///
+    /// Uses power iteration to approximate the dominant eigenvalue, with a
+    /// generous budget (`global_coherence_detailed(1e-12, 1000)`). The
+    /// estimate is returned even if that budget runs out — use the detailed
+    /// form or `try_global_coherence` to detect non-convergence.
+    pub fn global_coherence(&self) -> f64 {
+        self.global_coherence_detailed(1e-12, 1000).gamma
+    }
+
+    /// Global coherence Γ with the dominant eigenvector and convergence.
+    ///
+    /// Stops once the eigenvalue estimate changes by less than `tol` *and*
+    /// the eigen-residual ‖𝕄v − λv‖ is below √tol. The residual guard
+    /// matters for disconnected matrices: there the vector may keep
+    /// oscillating between components while λ looks settled, and such a
+    /// run reports `converged = false`. The eigenvector is then only
+    /// meaningful per component.
+    pub fn global_coherence_detailed(&self, tol: f64, max_iter: usize) -> CoherenceSpectrum {
+        if self.n == 0 {
+            return CoherenceSpectrum {
+                gamma: 0.0,
+                lambda_max: 0.0,
+                eigenvector: Vec::new(),
+                iterations: 0,
+                converged: true,
+            };
+        }
+        let run = self.power_iteration_until(max_iter, settled(tol), |_, _| {});
+        CoherenceSpectrum {
+            gamma: run.lambda / self.n as f64,
+            lambda_max: run.lambda,
+            eigenvector: run.vector,
+            iterations: run.iterations,
+            converged: run.converged,
+        }
+    }
+
+    /// Global coherence Γ that fails instead of returning an unconverged value.
+    ///
+    /// Converged has the meaning of `global_coherence_detailed`: within
+    /// `max_iter` iterations λ moved by less than `tol` and the
+    /// eigen-residual ‖𝕄v − λv‖ dropped below √tol.
+    pub fn try_global_coherence(
+        &self,
+        tol: f64,
+        max_iter: usize,
+    ) -> Result<f64, NotConverged> {
+        if self.n == 0 {
+            return Ok(0.0);
+        }
+        let run = self.power_iteration_until(max_iter, settled(tol), |_, _| {});
+        let gamma = run.lambda / self.n as f64;
+        if run.converged {
+            Ok(gamma)
+        } else {
+            sic_warn!(
+                "power iteration did not converge: residual {:e} after {} iterations",
+                run.residual,
+                run.iterations
+            );
+            Err(NotConverged {
+                estimate: gamma,
+                residual: run.residual,
+                iterations: run.iterations,
+            })
+        }
+    }
+
+    /// Power iteration states for animating convergence.
+    ///
+    /// Returns (λ estimate, normalized vector) after each of `steps`
+    /// iterations; Γ = λ / N. Runs all steps without early stopping.
+    pub fn global_coherence_trace(&self, steps: usize) -> Vec<(f64, Vec<f64>)> {
+        let mut trace = Vec::with_capacity(steps);
+        if self.n > 0 {
+            self.power_iteration_until(steps, |_, _| false, |lambda, v| {
+                trace.push((lambda, v.to_vec()))
+            });
+        }
+        trace
+    }
+
+    /// 𝕄v over the upper triangle. Rows split across threads under the
+    /// `rayon` feature; each row still sums in column order, so the
+    /// result is bit-identical to the serial product.
+    fn multiply(&self, v: &[f64]) -> Vec<f64> {
+        let row = |i: usize| (0..self.n).fold(0.0, |acc, j| acc + self.link(i, j) * v[j]);
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            (0..self.n).into_par_iter().map(row).collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            (0..self.n).map(row).collect()
+        }
+    }
+
+    /// Power iteration stopping once `done(|Δλ|, residual)` holds.
+    fn power_iteration_until(
+        &self,
+        max_iter: usize,
+        done: impl Fn(f64, f64) -> bool,
+        observe: impl FnMut(f64, &[f64]),
+    ) -> PowerIteration {
+        power_iterate(self.n, max_iter, |v| self.multiply(v), done, observe)
+    }
+
+    /// Local collapse analysis for a specific cluster (Section 11.5).
+    ///
+    /// Returns (gamma_k, collapsed) where gamma_k = λ₁⁽ᵏ⁾ / Tr(𝕄ₖ), with
+    /// λ₁⁽ᵏ⁾ from power iteration under the stopping rule and budget of
+    /// `global_coherence` (`tol` 1e-12, 1000 iterations).
+    pub fn local_collapse(
+        &self,
+        cluster_indices: &[usize],
+        theta: impl Into<Coherence>,
+    ) -> (f64, bool) {
+        let theta = theta.into().value();
+        let n_k = cluster_indices.len();
+        if n_k <= 1 {
+            return (1.0, true);
         }
 
         // Extract submatrix
@@ -150,28 +1345,1207 @@ impl CoherenceMatrix {
         }
 
         // Power iteration on submatrix
-        let mut v = vec![1.0 / (n_k as f64).sqrt(); n_k];
-        let mut lambda = 0.0;
+        let multiply = |v: &[f64]| -> Vec<f64> {
+            sub.iter()
+                .map(|row| row.iter().zip(v).map(|(m, x)| m * x).sum())
+                .collect()
+        };
+        let lambda = power_iterate(n_k, 1000, multiply, settled(1e-12), |_, _| {}).lambda;
 
-        for _ in 0..100 {
-            let mut w = vec![0.0; n_k];
-            for i in 0..n_k {
-                for j in 0..n_k {
-                    w[i] += sub[i][j] * v[j];
+        let trace: f64 = (0..n_k).map(|i| sub[i][i]).sum();
+        let gamma_k = if trace > 0.0 { lambda / trace } else { 0.0 };
+        (gamma_k, gamma_k > theta)
+    }
+
+    /// Serialize 𝕄 to a compact little-endian binary layout.
+    ///
+    /// Layout: n as u64, then the upper triangle (diagonal included) row by
+    /// row as f64 — symmetry (Axiom 5) halves the size to ≈ n(n+1)/2 × 8 bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8 + self.n * (self.n + 1) / 2 * 8);
+        bytes.extend_from_slice(&(self.n as u64).to_le_bytes());
+        for i in 0..self.n {
+            for j in i..self.n {
+                bytes.extend_from_slice(&self.data[i][j].to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Rebuild 𝕄 from the layout produced by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MatrixDecodeError> {
+        if bytes.len() < 8 {
+            return Err(MatrixDecodeError::MissingHeader);
+        }
+        let (header, payload) = bytes.split_at(8);
+        let n = u64::from_le_bytes(header.try_into().unwrap()) as usize;
+        let expected = n
+            .checked_add(1)
+            .and_then(|m| n.checked_mul(m))
+            .and_then(|x| (x / 2).checked_mul(8))
+            .unwrap_or(usize::MAX);
+        if payload.len() != expected {
+            return Err(MatrixDecodeError::LengthMismatch {
+                expected,
+                found: payload.len(),
+            });
+        }
+
+        let mut data = vec![vec![0.0; n]; n];
+        let mut values = payload
+            .chunks_exact(8)
+            .map(|c| f64::from_le_bytes(c.try_into().unwrap()));
+        for i in 0..n {
+            for j in i..n {
+                let v = values.next().unwrap();
+                data[i][j] = v;
+                data[j][i] = v;
+            }
+        }
+        Ok(Self {
+            data,
+            n,
+            distances: None,
+        })
+    }
+
+    /// Condition number estimate κ = |λ|_max / |λ|_min of the submatrix 𝕄ₖ.
+    ///
+    /// Computed from the full spectrum via the Jacobi eigenvalue method.
+    /// A nearly singular submatrix yields a huge κ (∞ if exactly singular),
+    /// meaning γ_k from `local_collapse` is numerically unreliable.
+    pub fn condition_estimate(&self, indices: &[usize]) -> f64 {
+        if indices.is_empty() {
+            return 1.0;
+        }
+        let sub: Vec<Vec<f64>> = indices
+            .iter()
+            .map(|&i| indices.iter().map(|&j| self.data[i][j]).collect())
+            .collect();
+        let (eigenvalues, _) = jacobi_eigen(&sub);
+        let max = eigenvalues.iter().map(|l| l.abs()).fold(0.0, f64::max);
+        let min = eigenvalues.iter().map(|l| l.abs()).fold(f64::INFINITY, f64::min);
+        if min > 0.0 {
+            max / min
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    /// `local_collapse` with a reliability flag.
+    ///
+    /// Returns (gamma_k, collapsed, low_confidence) where low_confidence is
+    /// set when the cluster's condition estimate exceeds `max_condition`.
+    pub fn local_collapse_checked(
+        &self,
+        cluster_indices: &[usize],
+        theta: f64,
+        max_condition: f64,
+    ) -> (f64, bool, bool) {
+        let (gamma_k, collapsed) = self.local_collapse(cluster_indices, theta);
+        let low_confidence = self.condition_estimate(cluster_indices) > max_condition;
+        (gamma_k, collapsed, low_confidence)
+    }
+
+    /// Suggest a friction ε (Section 11.4).
+    ///
+    /// Sorts the off-diagonal coherences and returns the midpoint of the
+    /// largest gap between consecutive values — the natural break between
+    /// intra- and inter-cluster entanglement. Returns 0.0 with fewer than
+    /// two distinct pairs.
+    pub fn suggest_epsilon(&self) -> f64 {
+        let mut values: Vec<f64> = (0..self.n)
+            .flat_map(|i| ((i + 1)..self.n).map(move |j| (i, j)))
+            .map(|(i, j)| self.data[i][j])
+            .collect();
+        if values.len() < 2 {
+            return 0.0;
+        }
+        values.sort_by(|a, b| a.total_cmp(b));
+
+        let mut best_gap = -1.0;
+        let mut epsilon = 0.0;
+        for pair in values.windows(2) {
+            let gap = pair[1] - pair[0];
+            if gap > best_gap {
+                best_gap = gap;
+                epsilon = (pair[0] + pair[1]) / 2.0;
+            }
+        }
+        epsilon
+    }
+
+    /// Run the full collapse workflow with a suggested ε (Sections 11.4–11.6).
+    pub fn full_analysis(&self, theta: f64) -> AnalysisReport {
+        self.full_analysis_with_epsilon(self.suggest_epsilon(), theta)
+    }
+
+    /// Run the full collapse workflow with an explicit ε: Γ before, friction,
+    /// clustering, per-cluster γ_k and verdicts, Γ after. `self` is untouched.
+    pub fn full_analysis_with_epsilon(&self, epsilon: f64, theta: f64) -> AnalysisReport {
+        let gamma_before = self.global_coherence();
+        let mut sparse = self.clone();
+        sparse.apply_friction(epsilon);
+        let clusters = cluster_groups(&sparse.find_clusters());
+
+        let (cluster_gammas, collapsed) = clusters
+            .iter()
+            .map(|indices| sparse.local_collapse(indices, theta))
+            .unzip();
+
+        AnalysisReport {
+            gamma_before,
+            epsilon,
+            gamma_after: sparse.global_coherence(),
+            clusters,
+            cluster_gammas,
+            collapsed,
+        }
+    }
+
+    /// Collapse phase diagram over the (ε, θ) plane (Sections 11.4–11.5).
+    ///
+    /// `grid[a][b]` is the number of clusters that collapse (γ_k > θ) after
+    /// friction ε = `epsilons[a]`, with θ = `thetas[b]`. `self` is untouched.
+    pub fn collapse_phase_diagram(&self, epsilons: &[f64], thetas: &[f64]) -> Vec<Vec<usize>> {
+        epsilons
+            .iter()
+            .map(|&epsilon| {
+                let mut sparse = self.clone();
+                sparse.apply_friction(epsilon);
+                let gammas: Vec<f64> = cluster_groups(&sparse.find_clusters())
+                    .iter()
+                    .map(|indices| sparse.local_collapse(indices, 0.0).0)
+                    .collect();
+                thetas
+                    .iter()
+                    .map(|&theta| gammas.iter().filter(|&&g| g > theta).count())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Suggest a collapse threshold θ for a set of clusters (Section 11.5).
+    ///
+    /// Computes γ_k for every cluster, sorts them, and returns the midpoint
+    /// of the largest gap between consecutive values — the θ that best
+    /// separates collapsed from superposed clusters. With fewer than two
+    /// clusters there is no gap to split and the default θ = 0.5 is returned.
+    pub fn suggest_theta(&self, clusters: &[Vec<usize>]) -> f64 {
+        let mut gammas: Vec<f64> = clusters
+            .iter()
+            .map(|indices| self.local_collapse(indices, 0.0).0)
+            .collect();
+        if gammas.len() < 2 {
+            return 0.5;
+        }
+        gammas.sort_by(|a, b| a.total_cmp(b));
+
+        let mut best_gap = -1.0;
+        let mut theta = 0.5;
+        for pair in gammas.windows(2) {
+            let gap = pair[1] - pair[0];
+            if gap > best_gap {
+                best_gap = gap;
+                theta = (pair[0] + pair[1]) / 2.0;
+            }
+        }
+        theta
+    }
+}
+
+/// Streaming cluster decomposition (Section 11.4) via union-find.
+///
+/// Nodes are inserted one at a time with their coherences to every
+/// previously inserted node. An edge with Coh ≥ `threshold` joins the new
+/// node to that cluster; a node bridging several clusters merges them.
+/// After any sequence of inserts, `labels` matches `find_clusters` on the
+/// full matrix with friction ε = `threshold` applied.
+#[derive(Debug, Clone)]
+pub struct IncrementalClusterer {
+    pub threshold: f64,
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+}
+
+impl IncrementalClusterer {
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            parent: Vec::new(),
+            rank: Vec::new(),
+        }
+    }
+
+    /// Number of nodes inserted so far.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Insert a node given `edges[j]` = Coh(new, j) for the existing nodes.
+    ///
+    /// Returns the cluster label of the new node. Labels are numbered in
+    /// order of each cluster's smallest member, so a merge may renumber
+    /// previously returned labels — call `labels` for the current view.
+    pub fn insert(&mut self, edges: &[f64]) -> usize {
+        assert!(
+            edges.len() <= self.len(),
+            "{} edges given but only {} nodes exist",
+            edges.len(),
+            self.len()
+        );
+        let node = self.parent.len();
+        self.parent.push(node);
+        self.rank.push(0);
+
+        for (j, &coh) in edges.iter().enumerate() {
+            if coh > 0.0 && coh >= self.threshold {
+                self.union(node, j);
+            }
+        }
+
+        self.labels()[node]
+    }
+
+    /// Current cluster labels, numbered like `find_clusters`.
+    pub fn labels(&mut self) -> Vec<usize> {
+        let n = self.parent.len();
+        let mut root_label = vec![usize::MAX; n];
+        let mut labels = vec![0; n];
+        let mut next = 0;
+        for i in 0..n {
+            let root = self.find(i);
+            if root_label[root] == usize::MAX {
+                root_label[root] = next;
+                next += 1;
+            }
+            labels[i] = root_label[root];
+        }
+        labels
+    }
+
+    fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // Path compression
+        let mut node = x;
+        while self.parent[node] != root {
+            let next = self.parent[node];
+            self.parent[node] = root;
+            node = next;
+        }
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return;
+        }
+        match self.rank[ra].cmp(&self.rank[rb]) {
+            std::cmp::Ordering::Less => self.parent[ra] = rb,
+            std::cmp::Ordering::Greater => self.parent[rb] = ra,
+            std::cmp::Ordering::Equal => {
+                self.parent[rb] = ra;
+                self.rank[ra] += 1;
+            }
+        }
+    }
+}
+
+/// One merge of a `Dendrogram`: the clusters whose smallest members are
+/// `left` < `right` join when the threshold drops to `threshold`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Merge {
+    pub threshold: f64,
+    pub left: usize,
+    pub right: usize,
+    /// Size of the joined cluster
+    pub size: usize,
+}
+
+/// Single-linkage cluster hierarchy of 𝕄, from `cluster_dendrogram`.
+///
+/// `merges` is ordered by non-increasing threshold; N minus the number
+/// of merges is the number of clusters left at threshold 0⁺.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dendrogram {
+    pub n: usize,
+    pub merges: Vec<Merge>,
+}
+
+impl Dendrogram {
+    /// Cluster labels at `threshold`: every merge at or above it applied,
+    /// numbered in ascending order of each cluster's smallest member.
+    /// Singletons are clusters of their own.
+    pub fn cut(&self, threshold: f64) -> Vec<usize> {
+        let mut sets = IncrementalClusterer::new(0.0);
+        for _ in 0..self.n {
+            sets.insert(&[]);
+        }
+        for merge in self.merges.iter().take_while(|m| m.threshold >= threshold) {
+            sets.union(merge.left, merge.right);
+        }
+        sets.labels()
+    }
+}
+
+/// Predicts coherence for unseen contexts from measured samples.
+///
+/// Useful when coherence is measured empirically rather than given by the
+/// kernel: predictions are Nadaraya–Watson kernel-weighted averages of the
+/// known values, with Gaussian weights w = exp(-d²/2h²) over the contextual
+/// distance d to each sample and bandwidth h.
+#[derive(Debug, Clone)]
+pub struct CoherenceRegressor {
+    pub bandwidth: f64,
+    samples: Vec<(Context, f64)>,
+}
+
+impl CoherenceRegressor {
+    /// Fit on known (context, coherence) pairs.
+    pub fn fit(samples: &[(Context, f64)], bandwidth: f64) -> Self {
+        Self {
+            bandwidth,
+            samples: samples.to_vec(),
+        }
+    }
+
+    /// Predicted coherence for `query`, clamped to [0, 1].
+    ///
+    /// Falls back to the nearest sample's value when the query is so far
+    /// from every sample that all weights underflow; 0.0 with no samples.
+    pub fn predict(&self, query: &Context) -> f64 {
+        let h2 = 2.0 * self.bandwidth * self.bandwidth;
+        let mut weighted = 0.0;
+        let mut total = 0.0;
+        let mut nearest = (f64::INFINITY, 0.0);
+        for (ctx, coh) in &self.samples {
+            let d = ctx.distance(query);
+            let w = (-d * d / h2).exp();
+            weighted += w * coh;
+            total += w;
+            if d < nearest.0 {
+                nearest = (d, *coh);
+            }
+        }
+        let prediction = if total > 0.0 { weighted / total } else { nearest.1 };
+        prediction.clamp(0.0, 1.0)
+    }
+}
+
+/// Eigen-decomposition of a symmetric matrix by cyclic Jacobi rotations.
+///
+/// Returns (eigenvalues, eigenvectors) where `eigenvectors[k]` is the unit
+/// eigenvector for `eigenvalues[k]`. Order is unspecified.
+fn jacobi_eigen(matrix: &[Vec<f64>]) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = matrix.len();
+    let mut a: Vec<Vec<f64>> = matrix.to_vec();
+    let mut v = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        v[i][i] = 1.0;
+    }
+
+    for _ in 0..100 {
+        let off: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum();
+        if off < 1e-22 {
+            break;
+        }
+        for p in 0..n {
+            for q in (p + 1)..n {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let t = if theta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let (akp, akq) = (a[k][p], a[k][q]);
+                    a[k][p] = c * akp - s * akq;
+                    a[k][q] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a[p][k], a[q][k]);
+                    a[p][k] = c * apk - s * aqk;
+                    a[q][k] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v[k][p], v[k][q]);
+                    v[k][p] = c * vkp - s * vkq;
+                    v[k][q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let eigenvalues = (0..n).map(|i| a[i][i]).collect();
+    let eigenvectors = (0..n).map(|k| (0..n).map(|i| v[i][k]).collect()).collect();
+    (eigenvalues, eigenvectors)
+}
+
+/// Minimal deterministic PRNG (SplitMix64) for seeded algorithms.
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// Centroid-based clustering of contexts in parameter space (Section 7.1).
+///
+/// Contexts are embedded in the unified space spanned by every parameter
+/// name in the set (missing parameters read as 0.0, as in `distance`).
+/// Centroids are seeded from `k` distinct contexts chosen by `seed`; a
+/// cluster that empties is reseeded from a random context. Returns the
+/// label per context and the centroid contexts, each inheriting the kind
+/// of its first member. `k` is capped at the number of contexts.
+pub fn kmeans_contexts(
+    contexts: &[Context],
+    k: usize,
+    iters: usize,
+    seed: u64,
+) -> (Vec<usize>, Vec<Context>) {
+    let n = contexts.len();
+    let k = k.min(n);
+    if k == 0 {
+        return (Vec::new(), Vec::new());
+    }
+
+    let mut names: Vec<&str> = contexts
+        .iter()
+        .flat_map(|c| c.params.keys().map(|s| s.as_str()))
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+    let points: Vec<Vec<f64>> = contexts
+        .iter()
+        .map(|c| names.iter().map(|name| c.param(name)).collect())
+        .collect();
+    let dist_sq = |a: &[f64], b: &[f64]| -> f64 {
+        a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum()
+    };
+
+    // Seed with k distinct contexts (partial Fisher–Yates)
+    let mut rng = SplitMix64(seed);
+    let mut order: Vec<usize> = (0..n).collect();
+    for i in 0..k {
+        let j = i + rng.below(n - i);
+        order.swap(i, j);
+    }
+    let mut centroids: Vec<Vec<f64>> = order[..k].iter().map(|&i| points[i].clone()).collect();
+    let mut labels = vec![0; n];
+
+    for _ in 0..iters.max(1) {
+        // Assignment step
+        let mut changed = false;
+        for (i, p) in points.iter().enumerate() {
+            let best = (0..k)
+                .min_by(|&a, &b| dist_sq(p, &centroids[a]).total_cmp(&dist_sq(p, &centroids[b])))
+                .unwrap();
+            if labels[i] != best {
+                labels[i] = best;
+                changed = true;
+            }
+        }
+
+        // Update step
+        let mut sums = vec![vec![0.0; names.len()]; k];
+        let mut counts = vec![0usize; k];
+        for (i, p) in points.iter().enumerate() {
+            counts[labels[i]] += 1;
+            for (s, x) in sums[labels[i]].iter_mut().zip(p) {
+                *s += x;
+            }
+        }
+        for c in 0..k {
+            if counts[c] == 0 {
+                // Empty cluster: reseed from a random context
+                let r = rng.below(n);
+                centroids[c] = points[r].clone();
+                labels[r] = c;
+                changed = true;
+            } else {
+                centroids[c] = sums[c].iter().map(|s| s / counts[c] as f64).collect();
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let centroid_contexts = centroids
+        .iter()
+        .enumerate()
+        .map(|(c, centroid)| {
+            let kind = labels
+                .iter()
+                .position(|&l| l == c)
+                .map(|i| contexts[i].kind.clone())
+                .unwrap_or_else(|| contexts[0].kind.clone());
+            let params: Vec<(&str, f64)> =
+                names.iter().copied().zip(centroid.iter().copied()).collect();
+            Context::with_params(kind, &params)
+        })
+        .collect();
+
+    (labels, centroid_contexts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tridiagonal 𝕄 with off-diagonal `a`; λ₂/λ₁ → 1 as a → 0.
+    fn tridiagonal(a: f64) -> CoherenceMatrix {
+        CoherenceMatrix {
+            data: vec![vec![1.0, a, 0.0], vec![a, 1.0, a], vec![0.0, a, 1.0]],
+            n: 3,
+            distances: None,
+        }
+    }
+
+    #[test]
+    fn try_global_coherence_reports_slow_convergence() {
+        let err = tridiagonal(1e-4).try_global_coherence(1e-12, 50).unwrap_err();
+        assert_eq!(err.iterations, 50);
+        assert!(err.residual > 1e-6);
+        assert!(tridiagonal(0.5).try_global_coherence(1e-12, 1000).is_ok());
+    }
+
+    #[test]
+    fn local_collapse_converges_like_global_coherence() {
+        let m = tridiagonal(0.5);
+        let (gamma_k, _) = m.local_collapse(&[0, 1, 2], 0.5);
+        // λ₁ = 1 + a√2 and Tr = 3
+        assert!((gamma_k - (1.0 + 0.5 * 2f64.sqrt()) / 3.0).abs() < 1e-9);
+        assert!((gamma_k - m.global_coherence()).abs() < 1e-12);
+    }
+
+    /// Two pairs linked at 0.9 and 0.4, joined by a 0.2 link, plus an
+    /// isolated node 4.
+    fn chain() -> CoherenceMatrix {
+        let mut data = vec![vec![0.0; 5]; 5];
+        for (i, j, w) in [(0, 2, 0.9), (1, 3, 0.4), (2, 3, 0.2)] {
+            data[i][j] = w;
+            data[j][i] = w;
+        }
+        for (i, row) in data.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        CoherenceMatrix { data, n: 5, distances: None }
+    }
+
+    #[test]
+    fn threshold_clusters_are_deterministic_and_keep_singletons() {
+        let m = chain();
+        assert_eq!(m.find_clusters(), vec![0, 0, 0, 0, 1]);
+        assert_eq!(m.clusters_at(0.3), vec![0, 1, 0, 1, 2]);
+        assert_eq!(m.clusters_at(0.5), vec![0, 1, 0, 2, 3]);
+        assert_eq!(m.find_clusters_threshold(0.5), m.clusters_at(0.5));
+    }
+
+    #[test]
+    fn dendrogram_cut_matches_clusters_at() {
+        let m = chain();
+        let tree = m.cluster_dendrogram();
+        assert_eq!(tree.merges.len(), 3);
+        for t in [1.0, 0.9, 0.5, 0.4, 0.3, 0.2, 0.1, 0.0] {
+            assert_eq!(tree.cut(t), m.clusters_at(t), "t = {t}");
+        }
+    }
+
+    fn quantum(energy: f64, momentum: f64) -> Context {
+        Context::with_params(
+            crate::context::ContextKind::Quantum,
+            &[("energy", energy), ("momentum", momentum)],
+        )
+    }
+
+    #[test]
+    fn one_kernel_reaches_every_constructor() {
+        let contexts = [quantum(100.0, 1.0), quantum(180.0, 3.0), quantum(420.0, 2.0)];
+        let config = SicConfig {
+            coherence: CoherenceParams::new(300.0, crate::context::DistanceMetric::Manhattan),
+            ..SicConfig::default()
+        };
+        let reference = CoherenceMatrix::from_contexts_with(&contexts, &config.coherence);
+        let mut builder = MatrixBuilder::with_config(&config);
+        builder.extend(contexts.iter().cloned());
+        for m in [
+            CoherenceMatrix::from_contexts_config(&contexts, &config),
+            CoherenceMatrix::from_contexts_with_distances(&contexts, &config),
+            builder.build(),
+        ] {
+            assert_eq!(m.data, reference.data);
+        }
+        assert!(reference.data[0][1] > 0.5, "σ = 300 keeps these contexts coherent");
+    }
+
+    #[test]
+    fn any_sigma_keeps_reflexivity_and_symmetry() {
+        let (a, b) = (quantum(100.0, 1.0), quantum(400.0, 2.0));
+        for sigma in [1e-3, 1.0, 5.0, 1e6] {
+            let params = CoherenceParams { sigma, ..CoherenceParams::default() };
+            assert_eq!(coherence_with(&a, &a, &params), 1.0);
+            assert_eq!(coherence_with(&a, &b, &params), coherence_with(&b, &a, &params));
+        }
+        for sigma in [0.0, -1.0, f64::NAN] {
+            let params = CoherenceParams { sigma, ..CoherenceParams::default() };
+            assert_eq!(coherence_with(&a, &a, &params), 1.0);
+            assert_eq!(coherence_with(&a, &b, &params), 0.0);
+        }
+    }
+
+    #[test]
+    fn aged_contexts_lose_coherence() {
+        let t0 = Instant::now();
+        let a = quantum(100.0, 1.0).created_at(t0);
+        let b = quantum(120.0, 1.0).created_at(t0);
+        let fresh = coherence_aged_at(&a, &b, 10.0, t0);
+        let aged = coherence_aged_at(&a, &b, 10.0, t0 + std::time::Duration::from_secs(5));
+        assert_eq!(fresh, coherence(&a, &b));
+        assert!(aged < fresh);
+        // both contexts decay by exp(-5/10)
+        assert!((aged - fresh * (-1.0f64).exp()).abs() < 1e-12);
+    }
+
+    /// The nine contexts of the demo: thermal, quantum and social groups.
+    fn demo_contexts() -> Vec<Context> {
+        use crate::context::ContextKind::*;
+        let mut contexts = Vec::new();
+        for t in [20.0, 22.0, 21.0, 23.0] {
+            contexts.push(Context::with_params(Thermal, &[("temperature", t)]));
+        }
+        for e in [100.0, 102.0, 101.0] {
+            contexts.push(Context::with_params(Quantum, &[("energy", e)]));
+        }
+        for d in [50.0, 52.0] {
+            contexts.push(Context::with_params(Social, &[("density", d)]));
+        }
+        contexts
+    }
+
+    #[test]
+    fn clusters_at_matches_friction_then_find_clusters() {
+        for m in [chain(), CoherenceMatrix::from_contexts(&demo_contexts())] {
+            for epsilon in [0.0, 0.1, 0.3, 0.5, 0.95, 1.0] {
+                let mut rubbed = m.clone();
+                rubbed.apply_friction(epsilon);
+                assert_eq!(m.clusters_at(epsilon), rubbed.find_clusters(), "ε = {epsilon}");
+            }
+        }
+    }
+
+    /// Disjoint pairs (2k, 2k+1) linked at `links[k]`; γ_k = (1 + w) / 2.
+    fn pairs(links: &[f64]) -> CoherenceMatrix {
+        let n = 2 * links.len();
+        let mut data = vec![vec![0.0; n]; n];
+        for (k, &w) in links.iter().enumerate() {
+            data[2 * k][2 * k + 1] = w;
+            data[2 * k + 1][2 * k] = w;
+        }
+        for (i, row) in data.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        CoherenceMatrix { data, n, distances: None }
+    }
+
+    #[test]
+    fn suggested_theta_splits_bimodal_gammas() {
+        let m = pairs(&[0.9, 0.1, 0.95, 0.15]);
+        let clusters = cluster_groups(&m.find_clusters());
+        let theta = m.suggest_theta(&clusters);
+        // modes at γ ≈ 0.55–0.575 and γ ≈ 0.95–0.975
+        assert!(theta > 0.575 && theta < 0.95, "θ = {theta}");
+        assert!((theta - (0.575 + 0.95) / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn binary_form_round_trips_at_half_size() {
+        let m = CoherenceMatrix::from_contexts(&demo_contexts());
+        let bytes = m.to_bytes();
+        assert_eq!(bytes.len(), 8 + 9 * 10 / 2 * 8);
+        let decoded = CoherenceMatrix::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.n, m.n);
+        assert_eq!(decoded.data, m.data);
+
+        assert_eq!(
+            CoherenceMatrix::from_bytes(&bytes[..4]).unwrap_err(),
+            MatrixDecodeError::MissingHeader
+        );
+        assert!(matches!(
+            CoherenceMatrix::from_bytes(&bytes[..bytes.len() - 8]),
+            Err(MatrixDecodeError::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn default_contexts_of_a_kind_are_fully_coherent() {
+        use crate::context::ContextKind::Thermal;
+        let (a, b) = (Context::new_with_defaults(Thermal), Context::new_with_defaults(Thermal));
+        assert!(!a.params.is_empty());
+        assert_eq!(coherence(&a, &b), 1.0);
+    }
+
+    #[test]
+    fn incremental_labels_match_full_clustering() {
+        let contexts = demo_contexts();
+        // interleave the groups so inserts bridge and merge clusters
+        let order = [0, 4, 7, 1, 5, 8, 2, 6, 3];
+        let contexts: Vec<Context> = order.iter().map(|&i| contexts[i].clone()).collect();
+        let m = CoherenceMatrix::from_contexts(&contexts);
+        for threshold in [0.1, 0.5, 0.9] {
+            let mut stream = IncrementalClusterer::new(threshold);
+            for i in 0..m.n {
+                stream.insert(&m.data[i][..i]);
+                let mut prefix = CoherenceMatrix::from_contexts(&contexts[..=i]);
+                prefix.apply_friction(threshold);
+                assert_eq!(stream.labels(), prefix.find_clusters(), "θ = {threshold}, i = {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn axiom_report_pinpoints_each_violation() {
+        assert!(tridiagonal(0.5).axiom_report(0.0, 0.0).is_valid());
+
+        let mut m = tridiagonal(0.5);
+        m.data[1][1] = 0.9;
+        let report = m.axiom_report(1e-12, 0.0);
+        assert!(!report.reflexive && report.symmetric && report.in_range);
+        assert_eq!(report.reflexivity_violation, Some((1, 1)));
+
+        let mut m = tridiagonal(0.5);
+        m.data[2][1] = 0.4;
+        let report = m.axiom_report(1e-12, 0.0);
+        assert!(report.reflexive && !report.symmetric && report.in_range);
+        assert_eq!(report.symmetry_violation, Some((1, 2)));
+        // within the symmetry tolerance the same matrix passes
+        assert!(m.axiom_report(1e-12, 0.2).is_valid());
+
+        let mut m = tridiagonal(0.5);
+        m.data[0][2] = -0.3;
+        m.data[2][0] = -0.3;
+        let report = m.axiom_report(1e-12, 0.0);
+        assert!(report.reflexive && report.symmetric && !report.in_range);
+        assert_eq!(report.range_violation, Some((0, 2)));
+    }
+
+    #[test]
+    fn borderline_singleton_is_absorbed() {
+        let mut m = tridiagonal(0.9);
+        m.data[1][2] = 0.3;
+        m.data[2][1] = 0.3;
+        let labels = m.clusters_at(0.5);
+        assert_eq!(labels, vec![0, 0, 1]);
+        assert_eq!(m.absorb_singletons(&labels, 0.2), vec![0, 0, 0]);
+        assert_eq!(m.absorb_singletons(&labels, 0.4), labels);
+    }
+
+    #[test]
+    fn kmeans_recovers_three_separated_groups() {
+        use crate::context::ContextKind::Custom;
+        let mut contexts = Vec::new();
+        for (cx, cy) in [(0.0, 0.0), (100.0, 0.0), (0.0, 100.0)] {
+            for (dx, dy) in [(0.0, 1.0), (1.0, -1.0), (-1.0, 0.0)] {
+                let params = [("x", cx + dx), ("y", cy + dy)];
+                contexts.push(Context::with_params(Custom("point".into()), &params));
+            }
+        }
+        // Lloyd iteration keeps a local optimum, so pick a seed whose
+        // initial draw lands in every group
+        let (labels, centroids) = kmeans_contexts(&contexts, 3, 50, 0);
+        assert_eq!(centroids.len(), 3);
+        assert_eq!(relabel_by_min_index(&labels), vec![0, 0, 0, 1, 1, 1, 2, 2, 2]);
+        let corner = &centroids[labels[3]];
+        assert_eq!((corner.param("x"), corner.param("y")), (100.0, 0.0));
+        assert_eq!(kmeans_contexts(&contexts, 3, 50, 0).0, labels);
+    }
+
+    #[test]
+    fn near_singular_cluster_is_flagged_low_confidence() {
+        let m = pairs(&[0.999_999, 0.5]);
+        // eigenvalues 1 ± a, so κ = (1 + a) / (1 − a)
+        assert!((m.condition_estimate(&[0, 1]) - 1_999_999.0).abs() < 1.0);
+        let (_, collapsed, low_confidence) = m.local_collapse_checked(&[0, 1], 0.5, 1e3);
+        assert!(collapsed && low_confidence);
+        let (_, collapsed, low_confidence) = m.local_collapse_checked(&[2, 3], 0.5, 1e3);
+        assert!(collapsed && !low_confidence);
+    }
+
+    #[test]
+    fn bridge_context_has_the_highest_betweenness() {
+        // cliques {0, 1, 2} and {4, 5, 6} joined through 2 — 3 — 4
+        let mut data = vec![vec![0.0; 7]; 7];
+        for group in [[0, 1, 2], [4, 5, 6]] {
+            for &i in &group {
+                for &j in &group {
+                    data[i][j] = 0.9;
                 }
             }
-            lambda = w.iter().zip(v.iter()).map(|(a, b)| a * b).sum();
-            let norm: f64 = w.iter().map(|x| x * x).sum::<f64>().sqrt();
-            if norm > 0.0 {
-                for x in &mut w {
-                    *x /= norm;
+        }
+        for (i, j) in [(2, 3), (3, 4)] {
+            data[i][j] = 0.5;
+            data[j][i] = 0.5;
+        }
+        for (i, row) in data.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        let m = CoherenceMatrix { data, n: 7, distances: None };
+
+        let scores = m.betweenness(0.1);
+        assert_eq!(scores, vec![0.0, 0.0, 8.0, 9.0, 8.0, 0.0, 0.0]);
+        // above the bridge links the graph splits and nothing lies between
+        assert!(m.betweenness(0.6).iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn full_analysis_reproduces_the_demo() {
+        let report = CoherenceMatrix::from_contexts(&demo_contexts())
+            .full_analysis_with_epsilon(0.1, 0.5);
+        let printed = |x: f64| format!("{x:.4}");
+        assert_eq!(printed(report.gamma_before), "0.4052");
+        assert_eq!(printed(report.gamma_after), "0.4052");
+        assert_eq!(report.clusters, vec![vec![0, 1, 2, 3], vec![4, 5, 6], vec![7, 8]]);
+        let gammas: Vec<String> = report.cluster_gammas.iter().map(|&g| printed(g)).collect();
+        assert_eq!(gammas, ["0.9117", "0.9500", "0.9261"]);
+        assert_eq!(report.collapsed, vec![true; 3]);
+    }
+
+    #[test]
+    fn single_linkage_recovers_the_demo_clusters() {
+        let m = CoherenceMatrix::from_contexts(&demo_contexts());
+        assert_eq!(m.single_linkage(3), vec![0, 0, 0, 0, 1, 1, 1, 2, 2]);
+        assert_eq!(m.single_linkage(1), vec![0; 9]);
+        assert_eq!(m.single_linkage(9), (0..9).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn more_friction_gives_more_smaller_collapsed_clusters() {
+        // four tight pairs (0.85 within) strung 6° apart (0.24 across)
+        let contexts: Vec<Context> = [0.0, 2.0, 8.0, 10.0, 16.0, 18.0, 24.0, 26.0]
+            .iter()
+            .map(|&t| {
+                Context::with_params(crate::context::ContextKind::Thermal, &[("temperature", t)])
+            })
+            .collect();
+        let m = CoherenceMatrix::from_contexts(&contexts);
+        let grid = m.collapse_phase_diagram(&[0.0, 0.5, 0.9], &[0.2, 0.5]);
+
+        let collapsed: Vec<usize> = grid.iter().map(|row| row[0]).collect();
+        assert!(collapsed.windows(2).all(|w| w[0] <= w[1]), "{collapsed:?}");
+        assert_eq!(collapsed, vec![1, 4, 8]);
+        // a stricter θ never collapses more clusters
+        assert!(grid.iter().all(|row| row[1] <= row[0]));
+
+        let largest = |epsilon| {
+            cluster_groups(&m.clusters_at(epsilon)).iter().map(Vec::len).max().unwrap()
+        };
+        assert_eq!([largest(0.0), largest(0.5), largest(0.9)], [8, 2, 1]);
+    }
+
+    #[test]
+    fn renaming_a_parameter_restores_coherence() {
+        use crate::context::ContextKind::Thermal;
+        let mut a = Context::with_params(Thermal, &[("temp", 25.0)]);
+        let b = Context::with_params(Thermal, &[("temperature", 25.0)]);
+        // unshared names compare against 0 on both axes
+        assert!(coherence(&a, &b) < 1e-6);
+        assert!(a.rename_param("temp", "temperature"));
+        assert_eq!(coherence(&a, &b), 1.0);
+        assert!(!a.rename_param("temp", "temperature"));
+    }
+
+    #[test]
+    fn regressor_predicts_the_kernel_on_unseen_contexts() {
+        use crate::context::ContextKind::Thermal;
+        let at = |t: f64| Context::with_params(Thermal, &[("temperature", t)]);
+        let reference = at(25.0);
+        let samples: Vec<(Context, f64)> = (0..=50)
+            .map(|t| {
+                let ctx = at(t as f64);
+                let coh = coherence(&ctx, &reference);
+                (ctx, coh)
+            })
+            .collect();
+        let regressor = CoherenceRegressor::fit(&samples, 0.5);
+
+        for t in [3.5, 17.25, 22.5, 25.5, 31.75, 48.5] {
+            let query = at(t);
+            let truth = coherence(&query, &reference);
+            let predicted = regressor.predict(&query);
+            assert!((predicted - truth).abs() < 0.03, "t = {t}: {predicted} vs {truth}");
+        }
+        assert_eq!(CoherenceRegressor::fit(&[], 0.5).predict(&reference), 0.0);
+    }
+
+    #[test]
+    fn symmetrizing_an_asymmetric_matrix_makes_it_valid() {
+        let m = CoherenceMatrix {
+            data: vec![vec![0.9, 0.6, 0.1], vec![0.4, 1.0, 0.3], vec![0.3, 0.5, 1.0]],
+            n: 3,
+            distances: None,
+        };
+        assert!(!m.is_valid());
+        let s = m.symmetrized();
+        assert!(s.is_valid());
+        assert_eq!(s.data[0][1], 0.5);
+        assert_eq!(s.data[1][0], 0.5);
+        assert_eq!((s.data[0][2], s.data[1][2]), (0.2, 0.4));
+        assert_eq!(s.data[0][0], 1.0);
+        assert_eq!(m.data[0][1], 0.6);
+    }
+
+    #[test]
+    fn trace_rises_monotonically_to_global_coherence() {
+        // a Gaussian kernel over Euclidean distances is PSD
+        let contexts: Vec<Context> = [100.0, 104.0, 109.0, 115.0, 122.0]
+            .iter()
+            .map(|&e| quantum(e, 1.0))
+            .collect();
+        let m = CoherenceMatrix::from_contexts(&contexts);
+        let trace = m.global_coherence_trace(300);
+        assert_eq!(trace.len(), 300);
+
+        let limit = m.global_coherence() * m.n as f64;
+        let (last, vector) = trace.last().unwrap();
+        assert!((last - limit).abs() < 1e-9);
+        assert!((vector.iter().map(|x| x * x).sum::<f64>() - 1.0).abs() < 1e-12);
+        for pair in trace.windows(2) {
+            assert!(pair[1].0 >= pair[0].0 - 1e-12);
+            assert!(pair[1].0 <= limit + 1e-9);
+        }
+        assert!(trace[0].0 < limit - 1e-3);
+    }
+
+    #[test]
+    fn retained_distances_match_context_distance() {
+        let contexts = demo_contexts();
+        let m = CoherenceMatrix::from_contexts_with_distances(&contexts, &SicConfig::default());
+        let d = m.distance_matrix().unwrap();
+        for (i, a) in contexts.iter().enumerate() {
+            for (j, b) in contexts.iter().enumerate() {
+                assert_eq!(d[i][j], a.distance(b), "({i}, {j})");
+            }
+        }
+        assert_eq!(m.data, CoherenceMatrix::from_contexts(&contexts).data);
+        assert!(CoherenceMatrix::from_contexts(&contexts).distance_matrix().is_none());
+    }
+
+    #[test]
+    fn mds_separates_three_tight_clusters() {
+        let contexts = demo_contexts();
+        let m = CoherenceMatrix::from_contexts_with_distances(&contexts, &SicConfig::default());
+        let points = m.mds_2d().unwrap();
+        assert_eq!(points.len(), 9);
+        let groups = [0, 0, 0, 0, 1, 1, 1, 2, 2];
+        let dist = |a: (f64, f64), b: (f64, f64)| (a.0 - b.0).hypot(a.1 - b.1);
+
+        let (mut within, mut between) = (0.0f64, f64::INFINITY);
+        for i in 0..9 {
+            for j in (i + 1)..9 {
+                let d = dist(points[i], points[j]);
+                if groups[i] == groups[j] {
+                    within = within.max(d);
+                } else {
+                    between = between.min(d);
                 }
             }
-            v = w;
         }
+        assert!(between > 10.0 * within, "within {within}, between {between}");
+        assert!(CoherenceMatrix::from_contexts(&contexts).mds_2d().is_none());
+    }
 
-        let trace: f64 = (0..n_k).map(|i| sub[i][i]).sum();
-        let gamma_k = if trace > 0.0 { lambda / trace } else { 0.0 };
-        (gamma_k, gamma_k > theta)
+    #[test]
+    fn sparsity_never_rises_with_friction() {
+        let m = CoherenceMatrix::from_contexts(&demo_contexts());
+        let epsilons: Vec<f64> = (0..=20).map(|k| k as f64 / 20.0).collect();
+        let curve = m.sparsity_curve(&epsilons);
+        assert_eq!(curve.len(), epsilons.len());
+        assert!(curve.windows(2).all(|w| w[1].1 <= w[0].1));
+        assert!(curve[0].1 > 0.0 && curve[0].1 <= 1.0);
+        assert_eq!(curve[20].1, 0.0);
+
+        // matches the edges friction actually keeps
+        let mut rubbed = m.clone();
+        rubbed.apply_friction(0.5);
+        let kept = (0..9)
+            .flat_map(|i| ((i + 1)..9).map(move |j| (i, j)))
+            .filter(|&(i, j)| rubbed.data[i][j] > 0.0)
+            .count();
+        assert_eq!(curve[10].1, kept as f64 / 36.0);
+    }
+
+    #[test]
+    fn round_off_asymmetry_passes_only_with_a_tolerance() {
+        let mut m = tridiagonal(0.5);
+        m.data[1][0] += 1e-14;
+        assert!(m.is_valid());
+        assert!(m.axiom_report(0.0, DEFAULT_SYMMETRY_TOLERANCE).is_valid());
+        let exact = m.axiom_report(0.0, 0.0);
+        assert!(!exact.symmetric);
+        assert_eq!(exact.symmetry_violation, Some((0, 1)));
+    }
+
+    #[test]
+    fn out_of_range_coherence_is_clamped_or_rejected() {
+        assert_eq!(Coherence::new(1.5), Coherence::ONE);
+        assert_eq!(Coherence::from(-0.2), Coherence::ZERO);
+        assert_eq!(Coherence::new(f64::NAN), Coherence::ZERO);
+        assert_eq!(Coherence::try_new(1.5), None);
+        assert_eq!(Coherence::try_new(f64::NAN), None);
+        assert_eq!(Coherence::try_new(0.25).map(Coherence::value), Some(0.25));
+        assert_eq!(Coherence::new(0.25).friction(), 0.75);
+    }
+
+    #[test]
+    fn correct_partition_beats_a_scrambled_one() {
+        let m = CoherenceMatrix::from_contexts(&demo_contexts());
+        let correct = [0, 0, 0, 0, 1, 1, 1, 2, 2];
+        let scrambled = [0, 1, 2, 0, 1, 2, 0, 1, 2];
+        assert!(m.silhouette(&correct) > 0.5);
+        assert!(m.silhouette(&correct) > m.silhouette(&scrambled));
+
+        assert!(m.cluster_cohesion(&correct).iter().all(|&c| c > 0.8));
+        assert!(m.cluster_separation(&correct) < 0.01);
+        assert!(m.cluster_separation(&scrambled) > m.cluster_separation(&correct));
+    }
+
+    #[test]
+    fn streamed_build_equals_from_contexts() {
+        let contexts = demo_contexts();
+        let mut builder = MatrixBuilder::new();
+        assert!(builder.is_empty());
+        builder.add(contexts[0].clone());
+        builder.extend(contexts[1..].iter().cloned());
+        assert_eq!(builder.len(), 9);
+        assert_eq!(builder.build().data, CoherenceMatrix::from_contexts(&contexts).data);
+    }
+
+    #[test]
+    fn star_hub_has_the_highest_pagerank() {
+        // hub 0 linked to five leaves that share nothing with each other
+        let n = 6;
+        let mut data = vec![vec![0.0; n]; n];
+        for leaf in 1..n {
+            data[0][leaf] = 0.8;
+            data[leaf][0] = 0.8;
+        }
+        for (i, row) in data.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        let m = CoherenceMatrix { data, n, distances: None };
+
+        for row in m.to_stochastic() {
+            assert!((row.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+        let rank = m.pagerank(0.85, 100);
+        assert!((rank.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        for leaf in 1..n {
+            assert!(rank[0] > rank[leaf], "{rank:?}");
+            assert!((rank[leaf] - rank[1]).abs() < 1e-12);
+        }
+
+        let empty = CoherenceMatrix { data: vec![vec![0.0; 2]; 2], n: 2, distances: None };
+        assert_eq!(empty.to_stochastic(), vec![vec![0.5; 2]; 2]);
+    }
+
+    #[test]
+    fn cluster_labels_follow_the_smallest_member() {
+        let demo = demo_contexts();
+        let mut m = CoherenceMatrix::from_contexts(&demo);
+        m.apply_friction(0.5);
+        assert_eq!(m.find_clusters(), vec![0, 0, 0, 0, 1, 1, 1, 2, 2]);
+        assert_eq!(m.find_clusters(), m.find_clusters());
+
+        // interleave the kinds: labels are still numbered by first appearance
+        let order = [7, 4, 0, 5, 1, 8, 2, 6, 3];
+        let shuffled: Vec<Context> = order.iter().map(|&i| demo[i].clone()).collect();
+        let labels = CoherenceMatrix::from_contexts(&shuffled).clusters_at(0.5);
+        assert_eq!(labels, vec![0, 1, 2, 1, 2, 0, 2, 1, 2]);
+        let mut next = 0;
+        for &label in &labels {
+            assert!(label <= next, "{labels:?}");
+            if label == next {
+                next += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn knn_graph_keeps_k_edges_and_recovers_the_demo() {
+        let demo = demo_contexts();
+        for k in 1..=3 {
+            let graph = CoherenceMatrix::knn_graph(&demo, k);
+            for i in 0..demo.len() {
+                assert!(graph.degree(i) >= k, "node {i} at k = {k}");
+                for (j, coh) in graph.neighbors(i) {
+                    assert_eq!(graph.get(j, i), coh, "symmetric");
+                }
+            }
+        }
+        let graph = CoherenceMatrix::knn_graph(&demo, 1);
+        assert_eq!(graph.find_clusters(), vec![0, 0, 0, 0, 1, 1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn verify_axioms_flags_the_cosine_triangle() {
+        use crate::context::{ContextKind::Custom, DistanceMetric};
+        let contexts: Vec<Context> = [(1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .iter()
+            .map(|&(x, y)| Context::with_params(Custom("v".into()), &[("x", x), ("y", y)]))
+            .collect();
+        let euclidean = verify_axioms(&contexts, &CoherenceParams::default());
+        assert!(euclidean.is_valid() && !euclidean.has_soft_violations());
+
+        // 1 − cos breaks the triangle inequality, but 𝕄 is still a valid matrix
+        let cosine = verify_axioms(&contexts, &CoherenceParams::new(1.0, DistanceMetric::Cosine));
+        assert!(cosine.is_valid());
+        assert!(cosine.has_soft_violations());
+        assert_eq!(cosine.triangle_violation, Some((0, 1, 2)));
+        assert_eq!(cosine.range_violation, None);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_build_is_bit_identical_to_serial() {
+        use crate::context::{ContextKind::Custom, DistanceMetric};
+        let mut rng = SplitMix64(1023);
+        let mut value = || (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64 * 100.0;
+        let contexts: Vec<Context> = (0..1000)
+            .map(|_| {
+                let params = [("x", value()), ("y", value()), ("z", value())];
+                Context::with_params(Custom("v".into()), &params)
+            })
+            .collect();
+
+        let cosine = CoherenceParams::new(1.0, DistanceMetric::Cosine);
+        for params in [CoherenceParams::default(), cosine] {
+            let serial = CoherenceMatrix::from_contexts_with(&contexts, &params);
+            let parallel = CoherenceMatrix::from_contexts_parallel_with(&contexts, &params);
+            assert_eq!(parallel.n, serial.n);
+            assert!(parallel.data == serial.data, "{params:?}");
+        }
     }
 }
@@ -0,0 +1,75 @@
+//! Crate-wide tunable constants — one place to configure SIC.
+//!
+//! `SicConfig::default()` reproduces the values the formalization was
+//! calibrated with; pass a custom config to the `*_config` constructors
+//! to retune coherence, perspective fusion, or the nested learning
+//! normalizers without touching the individual modules.
+
+use crate::context::DistanceMetric;
+
+/// Characteristic coherence length σ (Section 9.2).
+pub const DEFAULT_SIGMA: f64 = 5.0;
+/// Perspective fusion threshold θ for ⊕_P (Section 6.1.1).
+pub const DEFAULT_FUSION_THRESHOLD: f64 = 0.5;
+/// Full-scale sensor error (10-bit ADC half range).
+pub const DEFAULT_SENSOR_FULL_SCALE: f64 = 512.0;
+/// Full-scale actuator movement per event, in degrees.
+pub const DEFAULT_MOVEMENT_FULL_SCALE: f64 = 5.0;
+/// Actuator step produced by a full-scale error at unit gain, in degrees.
+pub const DEFAULT_MAX_STEP: f64 = 10.0;
+/// Largest |𝕄ᵢⱼ − 𝕄ⱼᵢ| still accepted as symmetric (float round-trip noise).
+pub const DEFAULT_SYMMETRY_TOLERANCE: f64 = 1e-12;
+
+/// Parameters of the coherence kernel Coh = exp(-d²/σ²).
+///
+/// Any σ > 0 keeps Axioms 4–5. A σ ≤ 0 (or NaN) is treated as the limit
+/// σ → 0⁺: Coh = 1 for identical contexts and 0 otherwise, never NaN.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoherenceParams {
+    /// Characteristic coherence length σ
+    pub sigma: f64,
+    /// Metric for the contextual distance d
+    pub metric: DistanceMetric,
+}
+
+impl Default for CoherenceParams {
+    fn default() -> Self {
+        Self {
+            sigma: DEFAULT_SIGMA,
+            metric: DistanceMetric::Euclidean,
+        }
+    }
+}
+
+impl CoherenceParams {
+    pub fn new(sigma: f64, metric: DistanceMetric) -> Self {
+        Self { sigma, metric }
+    }
+}
+
+/// Centralized normalization constants.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SicConfig {
+    /// Coherence kernel used by every `*_config` constructor and ⊕
+    pub coherence: CoherenceParams,
+    /// Coherence above which perspectives fuse in ⊕_P
+    pub fusion_threshold: f64,
+    /// Sensor error mapped to a normalized error of 1.0
+    pub sensor_full_scale: f64,
+    /// Movement mapped to a normalized movement of 1.0
+    pub movement_full_scale: f64,
+    /// Reactive step for a full-scale error at unit gain
+    pub max_step: f64,
+}
+
+impl Default for SicConfig {
+    fn default() -> Self {
+        Self {
+            coherence: CoherenceParams::default(),
+            fusion_threshold: DEFAULT_FUSION_THRESHOLD,
+            sensor_full_scale: DEFAULT_SENSOR_FULL_SCALE,
+            movement_full_scale: DEFAULT_MOVEMENT_FULL_SCALE,
+            max_step: DEFAULT_MAX_STEP,
+        }
+    }
+}
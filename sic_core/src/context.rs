@@ -5,10 +5,20 @@
 //!
 //! The parametrization is relative to a reference perspective P_ref (Section 7.2).
 
-use std::collections::HashMap;
+use crate::config::DEFAULT_FUSION_THRESHOLD;
+use crate::decay::ExponentialDecay;
+use crate::time::Instant;
+use crate::units::UnitRegistry;
+use crate::Map;
+use core::fmt;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::float::Float;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 /// The kind of context — extensible classification.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ContextKind {
     Physical,
     Social,
@@ -20,77 +30,584 @@ pub enum ContextKind {
     Custom(String),
 }
 
+impl ContextKind {
+    /// Standard parameters for each kind (Section 7.1 examples).
+    /// Kinds without a canonical parametrization return an empty table.
+    pub fn default_params(&self) -> Vec<(&'static str, f64)> {
+        match self {
+            ContextKind::Thermal => vec![("temperature", 25.0), ("pressure", 1.0)],
+            ContextKind::Social => vec![("density", 0.7), ("connectivity", 0.4)],
+            ContextKind::Quantum => vec![("energy", 3.2), ("momentum", 1.1)],
+            ContextKind::Inertial => vec![("velocity", 0.0)],
+            ContextKind::Accelerated => vec![("acceleration", 9.81)],
+            ContextKind::Physical | ContextKind::Conceptual | ContextKind::Custom(_) => {
+                Vec::new()
+            }
+        }
+    }
+}
+
 /// A Context with continuous parametrization.
 ///
 /// Owns its parameters — only one context can hold these values at a time.
 /// This models contextual exclusivity via Rust's ownership system.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Context {
     pub kind: ContextKind,
     /// Continuous parameters θᵢ ∈ ℝ (Section 7.1)
-    pub params: HashMap<String, f64>,
+    pub params: Map<String, f64>,
+    /// Creation epoch — `None` means the context does not age.
+    #[cfg_attr(feature = "serde", serde(with = "crate::instant_serde::option"))]
+    pub created: Option<Instant>,
+    /// Alias → canonical parameter name, so heterogeneous sources that
+    /// name the same dimension differently still share it.
+    pub aliases: Map<String, String>,
+    /// Parameter → unit annotation; annotated parameters are converted to
+    /// canonical units (`UnitRegistry::standard`) before being combined.
+    pub units: Map<String, String>,
 }
 
 impl Context {
     pub fn new(kind: ContextKind) -> Self {
         Self {
             kind,
-            params: HashMap::new(),
+            params: Map::new(),
+            created: None,
+            aliases: Map::new(),
+            units: Map::new(),
         }
     }
 
+    /// The empty context — no parameters, so it is neutral for `union`.
+    pub fn empty(kind: ContextKind) -> Self {
+        Self::new(kind)
+    }
+
     /// Create a context with parameters.
     /// Example: Context::with_params(Thermal, [("temperature", 25.0), ("pressure", 1.0)])
+    ///
+    /// A repeated key keeps its last value; use `with_params_checked` to
+    /// reject duplicates instead.
     pub fn with_params(kind: ContextKind, params: &[(&str, f64)]) -> Self {
-        let mut map = HashMap::new();
+        let mut map = Map::new();
         for (key, val) in params {
-            map.insert(key.to_string(), *val);
+            if val.is_nan() {
+                sic_warn!("context parameter {:?} is NaN", key);
+            }
+            if map.insert(key.to_string(), *val).is_some() {
+                sic_warn!("context parameter {:?} given twice, keeping the last value", key);
+            }
         }
-        Self { kind, params: map }
+        Self {
+            kind,
+            params: map,
+            created: None,
+            aliases: Map::new(),
+            units: Map::new(),
+        }
+    }
+
+    /// Create a context with parameters, failing on the first repeated key.
+    pub fn with_params_checked(
+        kind: ContextKind,
+        params: &[(&str, f64)],
+    ) -> Result<Self, DuplicateKey> {
+        for (i, (key, _)) in params.iter().enumerate() {
+            if params[..i].iter().any(|(earlier, _)| earlier == key) {
+                return Err(DuplicateKey(key.to_string()));
+            }
+        }
+        Ok(Self::with_params(kind, params))
+    }
+
+    /// Create a context with parameters, failing on a repeated key or a
+    /// non-finite value.
+    pub fn try_with_params(
+        kind: ContextKind,
+        params: &[(&str, f64)],
+    ) -> Result<Self, ContextError> {
+        let context = Self::with_params_checked(kind, params)?;
+        context.validate()?;
+        Ok(context)
+    }
+
+    /// Reject NaN and infinite parameters, naming the first offending key
+    /// in key order.
+    pub fn validate(&self) -> Result<(), ContextError> {
+        match self.params_sorted().into_iter().find(|(_, val)| !val.is_finite()) {
+            Some((key, value)) => Err(ContextError::NonFinite {
+                key: key.to_string(),
+                value,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Create a context pre-populated with its kind's default parameters.
+    /// Override individual values through `params` afterwards.
+    pub fn new_with_defaults(kind: ContextKind) -> Self {
+        let defaults = kind.default_params();
+        Self::with_params(kind, &defaults)
+    }
+
+    /// Build one context per row of a columnar dataset.
+    /// `names[k]` labels column k, so row r becomes (kind, names[k] = rows[r][k]).
+    ///
+    /// Panics if any row's length differs from `names.len()`.
+    pub fn from_columns(kind: ContextKind, names: &[&str], rows: &[Vec<f64>]) -> Vec<Context> {
+        rows.iter()
+            .enumerate()
+            .map(|(r, row)| {
+                assert_eq!(
+                    row.len(),
+                    names.len(),
+                    "row {} has {} values but {} column names were given",
+                    r,
+                    row.len(),
+                    names.len()
+                );
+                let params: Vec<(&str, f64)> =
+                    names.iter().copied().zip(row.iter().copied()).collect();
+                Context::with_params(kind.clone(), &params)
+            })
+            .collect()
+    }
+
+    /// Stamp the context with a creation epoch so it ages (Section 7.5).
+    pub fn created_at(mut self, created: Instant) -> Self {
+        self.created = Some(created);
+        self
+    }
+
+    /// Seconds elapsed since creation, or 0.0 if the context is unstamped.
+    #[cfg(feature = "std")]
+    pub fn age_secs(&self) -> f64 {
+        self.age_secs_at(Instant::now())
+    }
+
+    /// Age measured against an explicit `now` (clock injection).
+    pub fn age_secs_at(&self, now: Instant) -> f64 {
+        match self.created {
+            Some(created) => now.saturating_duration_since(created).as_secs_f64(),
+            None => 0.0,
+        }
+    }
+
+    /// Influence weight of the context after temporal decay.
+    /// w(t) = exp(-age/τ)
+    pub fn decay_weight_at(&self, tau: f64, now: Instant) -> f64 {
+        ExponentialDecay::new(tau).factor(self.age_secs_at(now))
     }
 
     /// Get a parameter value, or 0.0 if not set.
+    /// Falls back to a parameter stored under an alias of `name`.
     pub fn param(&self, name: &str) -> f64 {
-        self.params.get(name).copied().unwrap_or(0.0)
+        if let Some(val) = self.params.get(name) {
+            return *val;
+        }
+        self.params
+            .iter()
+            .find(|(key, _)| self.aliases.get(*key).map(String::as_str) == Some(name))
+            .map(|(_, val)| *val)
+            .unwrap_or(0.0)
+    }
+
+    /// Parameters sorted by name, borrowing the keys — a deterministic
+    /// order for iteration and serialization. Not cached: `params` is
+    /// public and may change between calls.
+    pub fn params_sorted(&self) -> Vec<(&str, f64)> {
+        let mut sorted: Vec<(&str, f64)> =
+            self.params.iter().map(|(name, &val)| (name.as_str(), val)).collect();
+        sorted.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        sorted
+    }
+
+    /// Rename a parameter, keeping its value and unit. Returns false if
+    /// `from` is absent. An existing parameter named `to` is overwritten.
+    pub fn rename_param(&mut self, from: &str, to: &str) -> bool {
+        match self.params.remove(from) {
+            Some(val) => {
+                self.params.insert(to.to_string(), val);
+                if let Some(unit) = self.units.remove(from) {
+                    self.units.insert(to.to_string(), unit);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drop a parameter (e.g. a noisy dimension), returning its value.
+    pub fn remove_param(&mut self, name: &str) -> Option<f64> {
+        self.units.remove(name);
+        self.params.remove(name)
+    }
+
+    /// Annotate a parameter with its unit (e.g. "eV").
+    pub fn set_unit(&mut self, name: &str, unit: &str) {
+        self.units.insert(name.to_string(), unit.to_string());
+    }
+
+    /// Unit annotation of a parameter, if any.
+    pub fn unit(&self, name: &str) -> Option<&str> {
+        self.units.get(name).map(String::as_str)
+    }
+
+    /// Copy of the context without the named parameter.
+    pub fn without_param(&self, name: &str) -> Context {
+        let mut ctx = self.clone();
+        ctx.remove_param(name);
+        ctx
+    }
+
+    /// Declare `alias` as another name for the `canonical` dimension.
+    /// Distance, coherence and union treat both names as one parameter.
+    pub fn add_alias(&mut self, alias: &str, canonical: &str) {
+        self.aliases.insert(alias.to_string(), canonical.to_string());
+    }
+
+    /// Alias tables of both contexts combined (self wins on conflicts).
+    fn merged_aliases(&self, other: &Context) -> Map<String, String> {
+        let mut aliases = other.aliases.clone();
+        aliases.extend(self.aliases.iter().map(|(a, c)| (a.clone(), c.clone())));
+        aliases
+    }
+
+    /// Parameters keyed by their canonical names, in canonical units.
+    pub(crate) fn canonical_params(
+        &self,
+        aliases: &Map<String, String>,
+    ) -> Map<String, f64> {
+        let converted;
+        let source = if self.units.is_empty() {
+            self
+        } else {
+            converted = UnitRegistry::standard().canonicalize(self);
+            &converted
+        };
+        source
+            .params
+            .iter()
+            .map(|(key, val)| (aliases.get(key).unwrap_or(key).clone(), *val))
+            .collect()
+    }
+
+    /// Unit annotations of both contexts keyed by canonical name, in
+    /// canonical units (self wins on conflicts).
+    fn merged_units(
+        &self,
+        other: &Context,
+        aliases: &Map<String, String>,
+    ) -> Map<String, String> {
+        let registry = UnitRegistry::standard();
+        let mut units = Map::new();
+        for (name, unit) in other.units.iter().chain(self.units.iter()) {
+            let canonical = registry.to_canonical(unit, 1.0).map_or(unit.as_str(), |(u, _)| u);
+            units.insert(aliases.get(name).unwrap_or(name).clone(), canonical.to_string());
+        }
+        units
     }
 
     /// Contextual distance to another context (Section 9.1).
     /// d(C₁, C₂) = √(Σ |θᵢ₁ - θᵢ₂|²) over shared parameters.
     pub fn distance(&self, other: &Context) -> f64 {
-        let mut sum_sq = 0.0;
-        // Compare shared parameters
-        for (key, val) in &self.params {
-            let other_val = other.params.get(key).copied().unwrap_or(0.0);
-            sum_sq += (val - other_val).powi(2);
-        }
-        // Parameters in other but not in self
-        for (key, val) in &other.params {
-            if !self.params.contains_key(key) {
-                sum_sq += val.powi(2);
-            }
+        self.distance_with(other, DistanceMetric::Euclidean)
+    }
+
+    /// Contextual distance under an explicit metric.
+    pub fn distance_with(&self, other: &Context, metric: DistanceMetric) -> f64 {
+        if self.aliases.is_empty()
+            && other.aliases.is_empty()
+            && self.units.is_empty()
+            && other.units.is_empty()
+        {
+            return param_distance(&self.params, &other.params, metric);
         }
-        sum_sq.sqrt()
+        let aliases = self.merged_aliases(other);
+        param_distance(
+            &self.canonical_params(&aliases),
+            &other.canonical_params(&aliases),
+            metric,
+        )
+    }
+
+    /// `distance`, after checking both contexts with `validate` — a
+    /// non-finite parameter would otherwise make d NaN or ∞.
+    pub fn checked_distance(&self, other: &Context) -> Result<f64, ContextError> {
+        self.validate()?;
+        other.validate()?;
+        Ok(self.distance(other))
     }
 
     /// Context union C₁ ∪ C₂ — for the ⊕ operator.
-    /// Takes the union of parameters, averaging shared ones.
+    /// Takes the union of parameters, averaging shared ones. Parameters
+    /// with units are combined in (and annotated with) canonical units.
     pub fn union(&self, other: &Context) -> Context {
-        let mut params = self.params.clone();
-        for (key, val) in &other.params {
+        self.union_weighted(other, 1.0, 1.0)
+    }
+
+    /// Context union with shared parameters averaged by weight:
+    /// θ = (w₁θ₁ + w₂θ₂) / (w₁ + w₂). Negative weights count as 0; if both
+    /// weights vanish the plain mean is used.
+    pub fn union_weighted(&self, other: &Context, w_self: f64, w_other: f64) -> Context {
+        self.merge(other, MergeStrategy::Average, KindInheritance::Left, w_self, w_other)
+    }
+
+    /// Context union combining shared parameters with `strategy`; the
+    /// kind is inherited from the left operand.
+    pub fn union_with(&self, other: &Context, strategy: MergeStrategy) -> Context {
+        self.union_with_kind(other, strategy, KindInheritance::Left)
+    }
+
+    /// Context union with both the merge strategy and the kind source chosen.
+    pub fn union_with_kind(
+        &self,
+        other: &Context,
+        strategy: MergeStrategy,
+        kind: KindInheritance,
+    ) -> Context {
+        self.merge(other, strategy, kind, 1.0, 1.0)
+    }
+
+    /// Union core; the weights only matter for `MergeStrategy::Average`.
+    pub(crate) fn merge(
+        &self,
+        other: &Context,
+        strategy: MergeStrategy,
+        kind: KindInheritance,
+        w_self: f64,
+        w_other: f64,
+    ) -> Context {
+        let (mut w1, mut w2) = (w_self.max(0.0), w_other.max(0.0));
+        if w1 + w2 <= 0.0 {
+            (w1, w2) = (1.0, 1.0);
+        }
+        let aliases = self.merged_aliases(other);
+        let mut params = self.canonical_params(&aliases);
+        for (key, val) in other.canonical_params(&aliases) {
             params
-                .entry(key.clone())
-                .and_modify(|v| *v = (*v + val) / 2.0)
-                .or_insert(*val);
+                .entry(key)
+                .and_modify(|v| *v = strategy.combine(*v, val, w1, w2))
+                .or_insert(val);
         }
+        let units = self.merged_units(other, &aliases);
+        let kind = match kind {
+            KindInheritance::Left => self.kind.clone(),
+            KindInheritance::Right => other.kind.clone(),
+        };
         Context {
-            kind: self.kind.clone(), // inherit from left operand
+            kind,
             params,
+            created: None,
+            aliases,
+            units,
+        }
+    }
+}
+
+/// How C₁ ∪ C₂ combines a parameter present in both contexts.
+/// Parameters present in only one are always copied unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// (w₁θ₁ + w₂θ₂) / (w₁ + w₂) — the plain mean for unweighted unions
+    #[default]
+    Average,
+    /// max(θ₁, θ₂)
+    Max,
+    /// min(θ₁, θ₂)
+    Min,
+    /// θ₁ + θ₂ — for additive quantities such as energies
+    Sum,
+    /// θ₁ — the left operand overrides
+    LeftWins,
+    /// θ₂ — the right operand overrides
+    RightWins,
+}
+
+impl MergeStrategy {
+    fn combine(self, left: f64, right: f64, w_left: f64, w_right: f64) -> f64 {
+        match self {
+            MergeStrategy::Average => (w_left * left + w_right * right) / (w_left + w_right),
+            MergeStrategy::Max => left.max(right),
+            MergeStrategy::Min => left.min(right),
+            MergeStrategy::Sum => left + right,
+            MergeStrategy::LeftWins => left,
+            MergeStrategy::RightWins => right,
+        }
+    }
+}
+
+/// Which operand of C₁ ∪ C₂ gives the union its kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KindInheritance {
+    #[default]
+    Left,
+    Right,
+}
+
+/// How per-parameter differences combine into a contextual distance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DistanceMetric {
+    /// √(Σ Δθᵢ²) — the metric of Section 9.1
+    #[default]
+    Euclidean,
+    /// Σ |Δθᵢ|
+    Manhattan,
+    /// max |Δθᵢ|
+    Chebyshev,
+    /// 1 − cos∠(θ₁, θ₂): compares direction only, in [0, 2]. Not a true
+    /// metric — it violates the triangle inequality. A zero vector is at
+    /// 0 from another zero vector and 1 from anything else.
+    Cosine,
+}
+
+impl DistanceMetric {
+    /// Combine aligned parameter pairs (θᵢ₁, θᵢ₂).
+    fn combine(self, pairs: impl Iterator<Item = (f64, f64)>) -> f64 {
+        let abs_diff = |(x, y): (f64, f64)| (x - y).abs();
+        match self {
+            DistanceMetric::Euclidean => pairs.map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt(),
+            DistanceMetric::Manhattan => pairs.map(abs_diff).sum(),
+            DistanceMetric::Chebyshev => pairs.map(abs_diff).fold(0.0, f64::max),
+            DistanceMetric::Cosine => {
+                let (dot, norm1, norm2) = pairs.fold((0.0, 0.0, 0.0), |(d, n1, n2), (x, y)| {
+                    (d + x * y, n1 + x * x, n2 + y * y)
+                });
+                match (norm1 > 0.0, norm2 > 0.0) {
+                    (false, false) => 0.0,
+                    (true, true) => (1.0 - dot / (norm1.sqrt() * norm2.sqrt())).clamp(0.0, 2.0),
+                    _ => 1.0,
+                }
+            }
+        }
+    }
+}
+
+/// Distance between parameter maps; missing parameters read as 0.
+fn param_distance(
+    a: &Map<String, f64>,
+    b: &Map<String, f64>,
+    metric: DistanceMetric,
+) -> f64 {
+    // Shared parameters and those only in a, then those only in b
+    let from_a = a
+        .iter()
+        .map(|(key, val)| (*val, b.get(key).copied().unwrap_or(0.0)));
+    let only_b = b
+        .iter()
+        .filter(|(key, _)| !a.contains_key(*key))
+        .map(|(_, val)| (0.0, *val));
+    metric.combine(from_a.chain(only_b))
+}
+
+/// Merge near-duplicate contexts before building 𝕄.
+///
+/// Contexts are visited in order; each joins the first group of the same
+/// kind whose seed (first member) lies within `tol` distance, otherwise it
+/// seeds a new group. Each group becomes one context whose parameters are
+/// the mean over the members defining them, taken after converting to
+/// canonical names and units; the group keeps every member's aliases and
+/// unit annotations (earlier members win on conflicts). Returns the
+/// deduplicated contexts and, for every original index, the index of its
+/// group.
+pub fn dedupe_contexts(contexts: &[Context], tol: f64) -> (Vec<Context>, Vec<usize>) {
+    let mut seeds: Vec<usize> = Vec::new();
+    let mut members: Vec<Vec<usize>> = Vec::new();
+    let mut mapping = Vec::with_capacity(contexts.len());
+
+    for (i, ctx) in contexts.iter().enumerate() {
+        let group = seeds.iter().position(|&s| {
+            contexts[s].kind == ctx.kind && contexts[s].distance(ctx) <= tol
+        });
+        match group {
+            Some(g) => {
+                members[g].push(i);
+                mapping.push(g);
+            }
+            None => {
+                seeds.push(i);
+                members.push(vec![i]);
+                mapping.push(seeds.len() - 1);
+            }
+        }
+    }
+
+    let merged = members
+        .iter()
+        .map(|group| {
+            // Aliases and canonical units of every member
+            let kind = contexts[group[0]].kind.clone();
+            let tables = group.iter().fold(Context::empty(kind.clone()), |acc, &i| {
+                acc.union_with(&contexts[i], MergeStrategy::LeftWins)
+            });
+            let mut sums: Map<String, (f64, usize)> = Map::new();
+            for &i in group {
+                for (key, val) in contexts[i].canonical_params(&tables.aliases) {
+                    let entry = sums.entry(key).or_insert((0.0, 0));
+                    entry.0 += val;
+                    entry.1 += 1;
+                }
+            }
+            Context {
+                kind,
+                params: sums
+                    .into_iter()
+                    .map(|(key, (sum, count))| (key, sum / count as f64))
+                    .collect(),
+                created: None,
+                aliases: tables.aliases,
+                units: tables.units,
+            }
+        })
+        .collect();
+
+    (merged, mapping)
+}
+
+/// A parameter name appeared more than once in a constructor's input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateKey(pub String);
+
+impl fmt::Display for DuplicateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "duplicate context parameter {:?}", self.0)
+    }
+}
+
+impl core::error::Error for DuplicateKey {}
+
+/// A context failed validation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContextError {
+    /// A parameter is NaN or infinite
+    NonFinite { key: String, value: f64 },
+    /// A parameter name appeared more than once
+    DuplicateKey(String),
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContextError::NonFinite { key, value } => {
+                write!(f, "context parameter {:?} is not finite ({})", key, value)
+            }
+            ContextError::DuplicateKey(key) => write!(f, "duplicate context parameter {:?}", key),
         }
     }
 }
 
+impl core::error::Error for ContextError {}
+
+impl From<DuplicateKey> for ContextError {
+    fn from(err: DuplicateKey) -> Self {
+        ContextError::DuplicateKey(err.0)
+    }
+}
+
 /// Scale — the observation level.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Scale {
     Quantum,
     Microscopic,
@@ -99,28 +616,78 @@ pub enum Scale {
     Cosmic,
     /// Custom scale with a characteristic size parameter.
     Custom(String, f64),
+    /// Multi-scale span retained by composition, ordered finest → coarsest.
+    Multi(Vec<Scale>),
 }
 
 impl Scale {
+    /// Characteristic length in metres, ordering scales from finest to
+    /// coarsest. `Custom` scales use their own size, so they slot in
+    /// between the built-in levels; `Cosmic` is the observable universe
+    /// (~1e26 m), so only a larger `Custom` size sorts coarser. A
+    /// multi-scale span ranks by its finest member.
+    pub fn characteristic_size(&self) -> f64 {
+        match self {
+            Scale::Quantum => 1e-10,
+            Scale::Microscopic => 1e-6,
+            Scale::Mesoscopic => 1e-3,
+            Scale::Human => 1.0,
+            Scale::Cosmic => 1e26,
+            Scale::Custom(_, size) => *size,
+            Scale::Multi(_) => self.finest().characteristic_size(),
+        }
+    }
+
     /// Scale intersection S₁ ∩ S₂ — for the ⊕ operator.
-    /// Returns the more restrictive (finer) scale.
+    /// Returns the more restrictive (finer) scale by characteristic size;
+    /// equal sizes keep the left operand.
     pub fn intersect(&self, other: &Scale) -> Scale {
-        let rank = |s: &Scale| -> u8 {
-            match s {
-                Scale::Quantum => 0,
-                Scale::Microscopic => 1,
-                Scale::Mesoscopic => 2,
-                Scale::Human => 3,
-                Scale::Cosmic => 4,
-                Scale::Custom(_, _) => 3,
-            }
-        };
-        if rank(self) <= rank(other) {
+        if self.characteristic_size() <= other.characteristic_size() {
             self.clone()
         } else {
             other.clone()
         }
     }
+
+    /// Scale span S₁ ∪ S₂ — keeps every scale the operands cover.
+    /// Equal scales stay single; otherwise the result is a flat `Multi`.
+    pub fn span(&self, other: &Scale) -> Scale {
+        let mut scales: Vec<Scale> = Vec::new();
+        for s in self.members().iter().chain(other.members().iter()) {
+            if !scales.contains(s) {
+                scales.push(s.clone());
+            }
+        }
+        if scales.len() == 1 {
+            return scales.remove(0);
+        }
+        scales.sort_by(|a, b| a.characteristic_size().total_cmp(&b.characteristic_size()));
+        Scale::Multi(scales)
+    }
+
+    /// The individual scales this scale covers.
+    pub fn members(&self) -> &[Scale] {
+        match self {
+            Scale::Multi(scales) => scales,
+            single => core::slice::from_ref(single),
+        }
+    }
+
+    /// The finest scale covered (self for a single scale).
+    pub fn finest(&self) -> &Scale {
+        match self {
+            Scale::Multi(scales) if !scales.is_empty() => &scales[0],
+            _ => self,
+        }
+    }
+
+    /// The coarsest scale covered (self for a single scale).
+    pub fn coarsest(&self) -> &Scale {
+        match self {
+            Scale::Multi(scales) if !scales.is_empty() => &scales[scales.len() - 1],
+            _ => self,
+        }
+    }
 }
 
 /// Perspective — the observer's viewpoint.
@@ -128,6 +695,7 @@ impl Scale {
 /// Axiom 3: Perspective is constitutive of manifested reality.
 /// R{C,S,P₁} ≠ R{C,S,P₂} when P₁ ≠ P₂
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Perspective {
     pub kind: PerspectiveKind,
     /// Weight for composition (Section 6.1.1)
@@ -135,6 +703,7 @@ pub struct Perspective {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PerspectiveKind {
     Objective,
     Subjective,
@@ -142,6 +711,11 @@ pub enum PerspectiveKind {
     Deterministic,
     Statistical,
     Custom(String),
+    /// Irreducible compound of incompatible perspectives coexisting
+    /// without fusing (Section 6.1.1): each constituent kind with its
+    /// share of the perspective weight. Shares sum to 1; constituents
+    /// are never themselves compounds.
+    Compound(Vec<(PerspectiveKind, f64)>),
 }
 
 impl Perspective {
@@ -153,28 +727,317 @@ impl Perspective {
     ///
     /// If Coh(P₁, P₂) > θ → weighted fusion (compatible)
     /// If Coh(P₁, P₂) ≤ θ → irreducible compound (coexist without fusing)
+    ///
+    /// A compound keeps every constituent: compounds are flattened rather
+    /// than nested, equal kinds pool their weights, and the compound's
+    /// weight is the sum w₁ + w₂ with each kind holding its share.
     pub fn compose(&self, other: &Perspective, coherence: f64) -> Perspective {
-        let theta = 0.5; // fusion threshold
+        self.compose_with_threshold(other, coherence, DEFAULT_FUSION_THRESHOLD)
+    }
+
+    /// Perspective composition ⊕_P with an explicit fusion threshold θ.
+    pub fn compose_with_threshold(
+        &self,
+        other: &Perspective,
+        coherence: f64,
+        theta: f64,
+    ) -> Perspective {
+        self.compose_biased(other, coherence, 0.5, theta)
+    }
+
+    /// Perspective composition ⊕_P privileging one observer.
+    ///
+    /// `self_bias` ∈ [0, 1] weighs the operands as b·w₁ and (1 − b)·w₂
+    /// (scaled so b = ½ gives `compose`). A fusion takes self's kind when
+    /// b ≥ ½ and other's otherwise; a compound holds the biased weights.
+    pub fn compose_weighted(
+        &self,
+        other: &Perspective,
+        coherence: f64,
+        self_bias: f64,
+    ) -> Perspective {
+        self.compose_biased(other, coherence, self_bias, DEFAULT_FUSION_THRESHOLD)
+    }
+
+    fn compose_biased(
+        &self,
+        other: &Perspective,
+        coherence: f64,
+        self_bias: f64,
+        theta: f64,
+    ) -> Perspective {
+        let bias = self_bias.clamp(0.0, 1.0);
+        let biased_self = 2.0 * bias * self.weight;
+        let biased_other = 2.0 * (1.0 - bias) * other.weight;
         if coherence > theta {
             // Weighted fusion
-            let total = self.weight + other.weight;
+            let kind = if bias >= 0.5 { &self.kind } else { &other.kind };
             Perspective {
-                kind: self.kind.clone(),
-                weight: total * coherence,
+                kind: kind.clone(),
+                weight: (biased_self + biased_other) * coherence,
             }
         } else {
-            // Irreducible compound — keep the dominant one
-            if self.weight >= other.weight {
-                Perspective {
-                    kind: self.kind.clone(),
-                    weight: self.weight,
-                }
-            } else {
-                Perspective {
-                    kind: other.kind.clone(),
-                    weight: other.weight,
-                }
+            // Irreducible compound — both constituents coexist
+            let scaled = |p: &Perspective, scale: f64| {
+                p.constituents().into_iter().map(move |(kind, w)| (kind, w * scale))
+            };
+            let parts = scaled(self, 2.0 * bias).chain(scaled(other, 2.0 * (1.0 - bias)));
+            Perspective::from_constituents(parts.collect()).unwrap_or(Perspective {
+                kind: self.kind.clone(),
+                weight: 0.0,
+            })
+        }
+    }
+
+    /// The constituent kinds with their absolute weights: the compound's
+    /// shares times its weight, or the perspective itself.
+    pub fn constituents(&self) -> Vec<(PerspectiveKind, f64)> {
+        match &self.kind {
+            PerspectiveKind::Compound(parts) => parts
+                .iter()
+                .map(|(kind, share)| (kind.clone(), share * self.weight))
+                .collect(),
+            kind => vec![(kind.clone(), self.weight)],
+        }
+    }
+
+    /// Rebuild a perspective from absolute constituent weights: equal kinds
+    /// pool, non-positive weights drop out, a lone kind stays plain and
+    /// several form a normalized `Compound`. `None` if nothing is left.
+    pub(crate) fn from_constituents(parts: Vec<(PerspectiveKind, f64)>) -> Option<Perspective> {
+        let mut pooled: Vec<(PerspectiveKind, f64)> = Vec::new();
+        for (kind, weight) in parts {
+            match pooled.iter_mut().find(|(k, _)| *k == kind) {
+                Some((_, w)) => *w += weight,
+                None => pooled.push((kind, weight)),
+            }
+        }
+        pooled.retain(|(_, w)| *w > 0.0);
+        let total: f64 = pooled.iter().map(|(_, w)| w).sum();
+        match pooled.len() {
+            0 => None,
+            1 => {
+                let (kind, weight) = pooled.remove(0);
+                Some(Perspective { kind, weight })
             }
+            _ => Some(Perspective {
+                kind: PerspectiveKind::Compound(
+                    pooled.into_iter().map(|(k, w)| (k, w / total)).collect(),
+                ),
+                weight: total,
+            }),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_columns_builds_one_context_per_row() {
+        let rows = vec![vec![20.0, 0.4], vec![25.0, 0.5], vec![30.0, 0.6]];
+        let names = ["temperature", "humidity"];
+        let contexts = Context::from_columns(ContextKind::Thermal, &names, &rows);
+        assert_eq!(contexts.len(), 3);
+        for (ctx, row) in contexts.iter().zip(&rows) {
+            assert_eq!(ctx.kind, ContextKind::Thermal);
+            assert_eq!(ctx.params.len(), 2);
+            assert_eq!(ctx.param("temperature"), row[0]);
+            assert_eq!(ctx.param("humidity"), row[1]);
+        }
+    }
+
+    #[test]
+    fn dedupe_merges_thermal_contexts_within_tolerance() {
+        let thermal = |t| Context::with_params(ContextKind::Thermal, &[("temperature", t)]);
+        let quantum = Context::with_params(ContextKind::Quantum, &[("temperature", 20.5)]);
+        let contexts = [thermal(20.0), thermal(20.5), quantum, thermal(21.0), thermal(40.0)];
+        let (merged, index) = dedupe_contexts(&contexts, 1.0);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(index, vec![0, 0, 1, 0, 2]);
+        assert_eq!(merged[0].kind, ContextKind::Thermal);
+        assert_eq!(merged[0].param("temperature"), 20.5);
+        assert_eq!(merged[1].kind, ContextKind::Quantum);
+    }
+
+    #[test]
+    fn removing_a_parameter_changes_the_distance() {
+        let thermal = |t, noise| {
+            Context::with_params(ContextKind::Thermal, &[("temperature", t), ("noise", noise)])
+        };
+        let (a, b) = (thermal(20.0, 7.0), thermal(23.0, 3.0));
+        assert_eq!(a.distance(&b), 5.0);
+
+        let mut quiet = a.clone();
+        assert_eq!(quiet.remove_param("noise"), Some(7.0));
+        assert_eq!(quiet.remove_param("noise"), None);
+        // b's noise now compares against 0
+        assert_eq!(quiet.distance(&b), 18f64.sqrt());
+        assert_eq!(quiet.distance(&b.without_param("noise")), 3.0);
+    }
+
+    #[test]
+    fn custom_scales_slot_in_by_size() {
+        let nano = Scale::Custom("nano".into(), 1e-9);
+        let giga = Scale::Custom("giga".into(), 1e9);
+        let beyond = Scale::Custom("multiverse".into(), 1e30);
+
+        // nano sits between Quantum and Microscopic, in either operand order
+        assert_eq!(nano.intersect(&Scale::Microscopic), nano);
+        assert_eq!(Scale::Microscopic.intersect(&nano), nano);
+        assert_eq!(Scale::Quantum.intersect(&nano), Scale::Quantum);
+        let span = Scale::Microscopic.span(&nano);
+        assert_eq!(span, Scale::Multi(vec![nano.clone(), Scale::Microscopic]));
+        assert_eq!(span, nano.span(&Scale::Microscopic));
+
+        // a gigametre is still finer than the observable universe
+        assert_eq!(giga.intersect(&Scale::Cosmic), giga);
+        assert_eq!(Scale::Human.intersect(&giga), Scale::Human);
+        assert_eq!(giga.span(&Scale::Cosmic).coarsest(), &Scale::Cosmic);
+
+        // only a size beyond ~1e26 m sorts above Cosmic
+        assert_eq!(beyond.intersect(&Scale::Cosmic), Scale::Cosmic);
+        assert_eq!(Scale::Cosmic.intersect(&beyond), Scale::Cosmic);
+        assert_eq!(Scale::Cosmic.span(&beyond).coarsest(), &beyond);
+        assert_eq!(beyond.span(&Scale::Cosmic).finest(), &Scale::Cosmic);
+    }
+
+    #[test]
+    fn duplicate_keys_are_reported() {
+        let params = [("t", 1.0), ("p", 3.0), ("t", 2.0)];
+        assert_eq!(
+            Context::with_params_checked(ContextKind::Thermal, &params).unwrap_err(),
+            DuplicateKey("t".to_string())
+        );
+        assert_eq!(
+            Context::try_with_params(ContextKind::Thermal, &params).unwrap_err(),
+            ContextError::DuplicateKey("t".to_string())
+        );
+
+        let unique = Context::with_params_checked(ContextKind::Thermal, &params[..2]).unwrap();
+        assert_eq!(unique.params.len(), 2);
+        // the unchecked constructor still keeps the last value
+        assert_eq!(Context::with_params(ContextKind::Thermal, &params).params["t"], 2.0);
+    }
+
+    #[test]
+    fn full_self_bias_adopts_self_kind() {
+        let trusted = Perspective::new(PerspectiveKind::Objective);
+        let inferred = Perspective { kind: PerspectiveKind::Statistical, weight: 5.0 };
+
+        let fused = trusted.compose_weighted(&inferred, 0.9, 1.0);
+        assert_eq!(fused.kind, PerspectiveKind::Objective);
+        assert!((fused.weight - 1.8).abs() < 1e-12, "only self's weight counts");
+
+        let flipped = trusted.compose_weighted(&inferred, 0.9, 0.0);
+        assert_eq!(flipped.kind, PerspectiveKind::Statistical);
+        assert!((flipped.weight - 9.0).abs() < 1e-12);
+
+        let even = trusted.compose_weighted(&inferred, 0.9, 0.5);
+        let plain = trusted.compose(&inferred, 0.9);
+        assert_eq!(even.kind, plain.kind);
+        assert_eq!(even.weight, plain.weight);
+    }
+
+    #[test]
+    fn params_sorted_is_lexicographic() {
+        let ctx = Context::with_params(
+            ContextKind::Thermal,
+            &[("temperature", 25.0), ("Pressure", 1.0), ("humidity", 0.4), ("t2", 3.0)],
+        );
+        let sorted = ctx.params_sorted();
+        let names: Vec<&str> = sorted.iter().map(|&(name, _)| name).collect();
+        assert_eq!(names, ["Pressure", "humidity", "t2", "temperature"]);
+        for (name, value) in sorted {
+            assert_eq!(ctx.params[name], value);
+        }
+    }
+
+    #[test]
+    fn sum_of_disjoint_params_is_the_plain_union() {
+        let heat = Context::with_params(ContextKind::Thermal, &[("temperature", 25.0)]);
+        let air_params = [("pressure", 1.0), ("humidity", 0.4)];
+        let air = Context::with_params(ContextKind::Thermal, &air_params);
+        assert_eq!(heat.union_with(&air, MergeStrategy::Sum).params, heat.union(&air).params);
+
+        let a = Context::with_params(ContextKind::Thermal, &[("temperature", 25.0), ("p", 1.0)]);
+        let b = Context::with_params(ContextKind::Quantum, &[("temperature", 30.0), ("e", 3.2)]);
+        for strategy in [MergeStrategy::Max, MergeStrategy::Min] {
+            let ab = a.union_with(&b, strategy);
+            let ba = b.union_with(&a, strategy);
+            assert_eq!(ab.params, ba.params, "{strategy:?}");
+        }
+        assert_eq!(a.union_with(&b, MergeStrategy::Max).params["temperature"], 30.0);
+        assert_eq!(a.union_with(&b, MergeStrategy::Min).params["temperature"], 25.0);
+
+        let right = a.union_with_kind(&b, MergeStrategy::RightWins, KindInheritance::Right);
+        assert_eq!(right.kind, ContextKind::Quantum);
+        assert_eq!(right.params["temperature"], 30.0);
+    }
+
+    #[test]
+    fn small_custom_scale_intersects_finer_than_human() {
+        let cell = Scale::Custom("cell".into(), 1e-5);
+        assert!(cell.characteristic_size() < Scale::Human.characteristic_size());
+        assert_eq!(Scale::Human.intersect(&cell), cell);
+        assert_eq!(cell.intersect(&Scale::Human), cell);
+        // still coarser than the built-in Microscopic level
+        assert_eq!(cell.intersect(&Scale::Microscopic), Scale::Microscopic);
+
+        // equal sizes keep the left operand
+        let metre = Scale::Custom("metre".into(), 1.0);
+        assert_eq!(metre.intersect(&Scale::Human), metre);
+        assert_eq!(Scale::Human.intersect(&metre), Scale::Human);
+    }
+
+    fn weighted(kind: PerspectiveKind, weight: f64) -> Perspective {
+        Perspective { kind, weight }
+    }
+
+    #[test]
+    fn compound_shares_are_normalized() {
+        let objective = weighted(PerspectiveKind::Objective, 1.0);
+        let statistical = weighted(PerspectiveKind::Statistical, 3.0);
+        let compound = objective.compose(&statistical, 0.2);
+        assert_eq!(compound.weight, 4.0);
+        assert_eq!(
+            compound.kind,
+            PerspectiveKind::Compound(vec![
+                (PerspectiveKind::Objective, 0.25),
+                (PerspectiveKind::Statistical, 0.75),
+            ])
+        );
+
+        // θ is a parameter: the same coherence fuses under a lower threshold
+        let strict = objective.compose_with_threshold(&statistical, 0.6, 0.7);
+        assert!(matches!(strict.kind, PerspectiveKind::Compound(_)));
+        let lenient = objective.compose_with_threshold(&statistical, 0.6, 0.5);
+        assert_eq!(lenient.kind, PerspectiveKind::Objective);
+    }
+
+    #[test]
+    fn compounds_merge_flat_in_either_grouping() {
+        let a = weighted(PerspectiveKind::Objective, 1.0);
+        let b = weighted(PerspectiveKind::Statistical, 2.0);
+        let c = weighted(PerspectiveKind::Subjective, 1.0);
+        let left = a.compose(&b, 0.1).compose(&c, 0.1);
+        let right = a.compose(&b.compose(&c, 0.1), 0.1);
+
+        assert_eq!(left.weight, 4.0);
+        assert_eq!(right.weight, 4.0);
+        let (left, right) = (left.constituents(), right.constituents());
+        assert_eq!(left.len(), 3, "no nested compounds");
+        for ((k1, w1), (k2, w2)) in left.iter().zip(&right) {
+            assert_eq!(k1, k2);
+            assert!((w1 - w2).abs() < 1e-12);
+        }
+
+        // a kind already in the compound pools instead of appearing twice
+        let pooled = a.compose(&b, 0.1).compose(&a, 0.1);
+        assert_eq!(
+            pooled.constituents(),
+            [(PerspectiveKind::Objective, 2.0), (PerspectiveKind::Statistical, 2.0)]
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Exponential temporal decay — Section 7.5
+//!
+//! decay(Δt) = value × exp(-Δt/τ)
+//!
+//! The single implementation of the decay law used by contexts and the
+//! nested learning memories; also usable on arbitrary user values.
+
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::float::Float;
+
+/// Exponential decay with time constant τ (seconds).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExponentialDecay {
+    pub tau: f64,
+}
+
+impl ExponentialDecay {
+    pub fn new(tau: f64) -> Self {
+        Self { tau }
+    }
+
+    /// Decay factor exp(-Δt/τ) ∈ [0, 1].
+    ///
+    /// τ ≤ 0 means immediate decay: the factor is 1 at Δt = 0 and 0 after.
+    /// τ = ∞ never decays, even over an infinite Δt.
+    pub fn factor(&self, dt: f64) -> f64 {
+        if self.tau <= 0.0 {
+            return if dt > 0.0 { 0.0 } else { 1.0 };
+        }
+        if self.tau == f64::INFINITY {
+            return 1.0;
+        }
+        (-dt.max(0.0) / self.tau).exp()
+    }
+
+    /// Decay `value` over Δt seconds.
+    pub fn apply(&self, value: f64, dt: f64) -> f64 {
+        value * self.factor(dt)
+    }
+
+    /// Time for a value to halve: τ · ln 2.
+    pub fn half_life(&self) -> f64 {
+        self.tau * core::f64::consts::LN_2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factor_is_one_at_zero_and_one_over_e_at_tau() {
+        let decay = ExponentialDecay::new(5.0);
+        assert_eq!(decay.factor(0.0), 1.0);
+        assert!((decay.factor(5.0) - (-1.0f64).exp()).abs() < 1e-15);
+        assert!((decay.apply(10.0, decay.half_life()) - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn degenerate_time_constants() {
+        let immediate = ExponentialDecay::new(0.0);
+        assert_eq!(immediate.factor(0.0), 1.0);
+        assert_eq!(immediate.factor(1e-9), 0.0);
+        assert_eq!(ExponentialDecay::new(f64::INFINITY).factor(f64::INFINITY), 1.0);
+    }
+}
@@ -8,7 +8,54 @@
 //! This is enforced at COMPILE TIME by Rust's borrow checker — modeling
 //! the SIC axiom that entities cannot outlive their context.
 
-use crate::context::{Context, Perspective, Scale};
+use crate::coherence::{coherence, CoherenceMatrix};
+use crate::context::{Context, ContextKind, Perspective, PerspectiveKind, Scale};
+use crate::decay::ExponentialDecay;
+use crate::operators::compose;
+
+/// Uniform read access to borrowed (`Entity`) and owned (`OwnedEntity`)
+/// entities, so the operators accept any combination of the two without
+/// `as_entity()` conversions.
+pub trait AsEntityRef {
+    fn context(&self) -> &Context;
+    fn scale(&self) -> &Scale;
+    fn perspective(&self) -> &Perspective;
+    fn intensity(&self) -> f64;
+
+    /// Accumulated Π Coh of the transforms this entity went through;
+    /// 1 for entities that do not track it.
+    fn fidelity(&self) -> f64 {
+        1.0
+    }
+
+    /// Provenance of the entity; empty for entities that do not track it.
+    fn history(&self) -> &[TransformStep] {
+        &[]
+    }
+}
+
+/// Operation recorded by a `TransformStep`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StepKind {
+    /// Context transformation T (Section 2.2)
+    Transform,
+    /// Contextual composition ⊕ (Section 6.1.2)
+    Compose,
+}
+
+/// One step of an entity's provenance.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransformStep {
+    pub operation: StepKind,
+    /// Context kind before T, or of the left operand of ⊕
+    pub source: ContextKind,
+    /// Context kind after T, or of the right operand of ⊕
+    pub target: ContextKind,
+    /// Attenuation applied by T, or Coh(C₁, C₂) for ⊕
+    pub coherence: f64,
+}
 
 /// A Contextual Entity E{C, S, P}.
 ///
@@ -66,17 +113,141 @@ impl<'ctx> Entity<'ctx> {
             intensity: self.intensity * alpha,
         }
     }
+
+    /// The entity after Δt seconds of temporal decay (Section 7.5):
+    /// I' = I · exp(-Δt/τ). τ ≤ 0 decays to zero at once; a huge Δt
+    /// underflows to 0, never NaN.
+    pub fn decayed(&self, delta_t_secs: f64, tau: f64) -> Entity<'ctx> {
+        self.modulate(ExponentialDecay::new(tau).factor(delta_t_secs))
+    }
+
+    /// Coherence between this entity's context and a target context —
+    /// the attenuation `transform` would apply. Same as
+    /// `coherence(self.context, target)`:
+    ///
+    /// ```
+    /// use sic_core::coherence::coherence;
+    /// use sic_core::context::{Context, ContextKind, Perspective, PerspectiveKind, Scale};
+    /// use sic_core::entity::Entity;
+    ///
+    /// let here = Context::with_params(ContextKind::Thermal, &[("temperature", 25.0)]);
+    /// let there = Context::with_params(ContextKind::Thermal, &[("temperature", 40.0)]);
+    /// let water = Entity::new(&here, Scale::Human, Perspective::new(PerspectiveKind::Objective));
+    ///
+    /// assert_eq!(water.coherence_to(&there), coherence(&here, &there));
+    /// ```
+    pub fn coherence_to(&self, target: &Context) -> f64 {
+        coherence(self.context, target)
+    }
+
+    /// Observe a context parameter through the entity's perspective (Axiom 3).
+    ///
+    /// The stored θ is the value under P_ref; each perspective applies its
+    /// own coordinate change φ(P, P_ref) (Section 7.2):
+    ///   - Objective, Deterministic: θ — they coincide with P_ref
+    ///   - Subjective, Custom: w·θ — scaled by the observer's weight
+    ///   - Intersubjective: (θ + w·θ) / 2 — shared between observer and P_ref
+    ///   - Statistical: (θ + θ̄) / 2 — regressed toward the mean θ̄ of all
+    ///     the context's parameters
+    ///   - Compound: the share-weighted mean of its constituents' views
+    pub fn observe(&self, name: &str) -> f64 {
+        self.observe_as(&self.perspective.kind, name)
+    }
+
+    fn observe_as(&self, kind: &PerspectiveKind, name: &str) -> f64 {
+        let theta = self.context.param(name);
+        let w = self.perspective.weight;
+        match kind {
+            PerspectiveKind::Objective | PerspectiveKind::Deterministic => theta,
+            PerspectiveKind::Subjective | PerspectiveKind::Custom(_) => w * theta,
+            PerspectiveKind::Intersubjective => (theta + w * theta) / 2.0,
+            PerspectiveKind::Statistical => {
+                let params = &self.context.params;
+                if params.is_empty() {
+                    return theta;
+                }
+                let mean = params.values().sum::<f64>() / params.len() as f64;
+                (theta + mean) / 2.0
+            }
+            PerspectiveKind::Compound(parts) => parts
+                .iter()
+                .map(|(part, share)| share * self.observe_as(part, name))
+                .sum(),
+        }
+    }
+}
+
+impl AsEntityRef for Entity<'_> {
+    fn context(&self) -> &Context {
+        self.context
+    }
+
+    fn scale(&self) -> &Scale {
+        &self.scale
+    }
+
+    fn perspective(&self) -> &Perspective {
+        &self.perspective
+    }
+
+    fn intensity(&self) -> f64 {
+        self.intensity
+    }
+}
+
+impl<'ctx> From<&Entity<'ctx>> for Entity<'ctx> {
+    fn from(entity: &Entity<'ctx>) -> Self {
+        entity.clone()
+    }
+}
+
+impl<'a> From<&'a OwnedEntity> for Entity<'a> {
+    fn from(entity: &'a OwnedEntity) -> Self {
+        entity.as_entity()
+    }
+}
+
+/// Serialized in `OwnedEntity` form (the context is written inline), so
+/// it loads back as an `OwnedEntity`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Entity<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(serde::Serialize)]
+        #[serde(rename = "OwnedEntity")]
+        struct Flattened<'a> {
+            context: &'a Context,
+            scale: &'a Scale,
+            perspective: &'a Perspective,
+            intensity: f64,
+            history: &'a [TransformStep],
+        }
+        Flattened {
+            context: self.context,
+            scale: &self.scale,
+            perspective: &self.perspective,
+            intensity: self.intensity,
+            history: &[],
+        }
+        .serialize(serializer)
+    }
 }
 
 /// An owned entity that holds its own context.
 /// Used when composition (⊕) creates a new entity with a new context
 /// that doesn't exist as a separate borrowed reference.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OwnedEntity {
     pub context: Context,
     pub scale: Scale,
     pub perspective: Perspective,
     pub intensity: f64,
+    /// Transforms and compositions that produced this entity, oldest
+    /// first; a composite lists its left operand's steps, then its right
+    /// operand's, then the ⊕ itself. Empty vectors do not allocate. The
+    /// entity's fidelity is derived from it (`total_attenuation`).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub history: Vec<TransformStep>,
 }
 
 impl OwnedEntity {
@@ -86,10 +257,73 @@ impl OwnedEntity {
             scale,
             perspective,
             intensity: 1.0,
+            history: Vec::new(),
+        }
+    }
+
+    /// The neutral entity ∅ for ⊕ (Section 6.1.2 — Identity).
+    ///
+    /// Empty context, coarsest scale, zero intensity and zero-weight
+    /// perspective, so `compose(x, ∅)` leaves x's parameters, scale and
+    /// intensity unchanged. It is only a near-identity:
+    ///   - the composed context kind is inherited from the left operand,
+    ///     so `compose(∅, x)` takes the neutral kind;
+    ///   - Coh(x, ∅) depends on x's parameter magnitude, so when it exceeds
+    ///     the fusion threshold the perspective weight is rescaled by it;
+    ///   - a `Custom` scale larger than Cosmic (~1e26 m) loses the
+    ///     intersection to it.
+    pub fn neutral() -> Self {
+        Self {
+            context: Context::empty(ContextKind::Custom("neutral".to_string())),
+            scale: Scale::Cosmic,
+            perspective: Perspective {
+                kind: PerspectiveKind::Custom("neutral".to_string()),
+                weight: 0.0,
+            },
+            intensity: 0.0,
+            history: Vec::new(),
         }
     }
 
+    /// Coherence between this entity's context and a target context.
+    pub fn coherence_to(&self, target: &Context) -> f64 {
+        coherence(&self.context, target)
+    }
+
+    /// Temporal decay of the intensity over Δt seconds, as
+    /// `Entity::decayed`: I ← I · exp(-Δt/τ).
+    pub fn decay(&mut self, delta_t_secs: f64, tau: f64) {
+        self.intensity = ExponentialDecay::new(tau).apply(self.intensity, delta_t_secs);
+    }
+
+    /// Relevance to a reference context: I × Coh(C, C_ref). High for
+    /// strong entities close to the reference.
+    pub fn rank_key(&self, reference: &Context) -> f64 {
+        self.intensity * self.coherence_to(reference)
+    }
+
+    /// Π of the coherence factors of every transform in the history —
+    /// the cumulative attenuation along all recorded paths (1 if none).
+    ///
+    /// This is the entity's fidelity: 1 when fresh, falling as repeated
+    /// transforms lose information. The ⊕ steps' Coh is fusion, not loss,
+    /// and is not counted, so a composite has the product of its
+    /// operands' fidelities.
+    pub fn total_attenuation(&self) -> f64 {
+        self.history
+            .iter()
+            .filter(|step| step.operation == StepKind::Transform)
+            .map(|step| step.coherence)
+            .product()
+    }
+
+    /// True once accumulated transform losses pushed fidelity below `threshold`.
+    pub fn is_degraded(&self, threshold: f64) -> bool {
+        self.total_attenuation() < threshold
+    }
+
     /// Borrow as a regular Entity (with lifetime tied to self).
+    /// The borrowed view does not carry fidelity.
     pub fn as_entity(&self) -> Entity<'_> {
         Entity {
             context: &self.context,
@@ -99,3 +333,163 @@ impl OwnedEntity {
         }
     }
 }
+
+impl AsEntityRef for OwnedEntity {
+    fn context(&self) -> &Context {
+        &self.context
+    }
+
+    fn scale(&self) -> &Scale {
+        &self.scale
+    }
+
+    fn perspective(&self) -> &Perspective {
+        &self.perspective
+    }
+
+    fn intensity(&self) -> f64 {
+        self.intensity
+    }
+
+    fn fidelity(&self) -> f64 {
+        self.total_attenuation()
+    }
+
+    fn history(&self) -> &[TransformStep] {
+        &self.history
+    }
+}
+
+/// Sort entities by descending `rank_key` against `reference`.
+pub fn sort_by_relevance(entities: &mut [OwnedEntity], reference: &Context) {
+    entities.sort_by(|a, b| b.rank_key(reference).total_cmp(&a.rank_key(reference)));
+}
+
+/// A collection of owned entities labelled with string tags
+/// (e.g. "sensor", "actuator") so operators can run over a tagged subset.
+#[derive(Debug, Clone, Default)]
+pub struct EntitySet {
+    members: Vec<(OwnedEntity, Vec<String>)>,
+}
+
+impl EntitySet {
+    pub fn new() -> Self {
+        Self {
+            members: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Add an entity with its tags, returning its index.
+    pub fn insert(&mut self, entity: OwnedEntity, tags: &[&str]) -> usize {
+        self.members.push((entity, tags.iter().map(|t| t.to_string()).collect()));
+        self.members.len() - 1
+    }
+
+    /// Members carrying `tag`, in insertion order.
+    pub fn tagged<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a OwnedEntity> + 'a {
+        self.members
+            .iter()
+            .filter(move |(_, tags)| tags.iter().any(|t| t == tag))
+            .map(|(entity, _)| entity)
+    }
+
+    /// ⊕ over the members carrying `tag`, folded left in insertion order.
+    /// `None` if no member carries the tag.
+    pub fn compose_tagged(&self, tag: &str) -> Option<OwnedEntity> {
+        let mut members = self.tagged(tag);
+        let first = members.next()?.clone();
+        Some(members.fold(first, |acc, e| compose(&acc, e)))
+    }
+
+    /// 𝕄 over the contexts of the members carrying `tag`.
+    pub fn coherence_matrix_for(&self, tag: &str) -> CoherenceMatrix {
+        let contexts: Vec<Context> = self.tagged(tag).map(|e| e.context.clone()).collect();
+        CoherenceMatrix::from_contexts(&contexts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn composing_with_neutral_keeps_the_entity() {
+        let params = [("temperature", 25.0), ("humidity", 0.4)];
+        let mut x = OwnedEntity::new(
+            Context::with_params(ContextKind::Thermal, &params),
+            Scale::Human,
+            Perspective::new(PerspectiveKind::Objective),
+        );
+        x.intensity = 3.0;
+        let composed = compose(&x, &OwnedEntity::neutral());
+        assert_eq!(composed.context.kind, x.context.kind);
+        assert_eq!(composed.context.params, x.context.params);
+        assert_eq!(composed.scale, x.scale);
+        assert_eq!(composed.perspective.kind, x.perspective.kind);
+        assert_eq!(composed.intensity, x.intensity);
+    }
+
+    #[test]
+    fn perspectives_observe_different_values() {
+        let params = [("temperature", 20.0), ("humidity", 60.0)];
+        let ctx = Context::with_params(ContextKind::Thermal, &params);
+        let view = |kind| Entity::new(&ctx, Scale::Human, Perspective::new(kind));
+        let (objective, statistical) =
+            (view(PerspectiveKind::Objective), view(PerspectiveKind::Statistical));
+        assert_eq!(objective.observe("temperature"), 20.0);
+        // regressed toward the mean of 20 and 60
+        assert_eq!(statistical.observe("temperature"), 30.0);
+        assert_eq!(ctx.param("temperature"), 20.0);
+    }
+
+    #[test]
+    fn compose_tagged_uses_only_the_tagged_members() {
+        let thermal = |t: f64, intensity: f64| {
+            let ctx = Context::with_params(ContextKind::Thermal, &[("temperature", t)]);
+            let objective = Perspective::new(PerspectiveKind::Objective);
+            let mut e = OwnedEntity::new(ctx, Scale::Human, objective);
+            e.intensity = intensity;
+            e
+        };
+        let mut set = EntitySet::new();
+        set.insert(thermal(20.0, 1.0), &["sensor"]);
+        set.insert(thermal(80.0, 100.0), &["actuator"]);
+        set.insert(thermal(22.0, 2.0), &["sensor", "indoor"]);
+        set.insert(thermal(21.0, 4.0), &["indoor", "sensor"]);
+        assert_eq!(set.tagged("sensor").count(), 3);
+
+        let sensors = set.compose_tagged("sensor").unwrap();
+        assert_eq!(sensors.intensity, 7.0);
+        let first_two = compose(&thermal(20.0, 1.0), &thermal(22.0, 2.0));
+        let by_hand = compose(&first_two, &thermal(21.0, 4.0));
+        assert_eq!(sensors.context.params, by_hand.context.params);
+        assert!(set.compose_tagged("missing").is_none());
+        assert_eq!(set.coherence_matrix_for("indoor").n, 2);
+    }
+
+    #[test]
+    fn strong_near_entity_ranks_ahead_of_a_weak_far_one() {
+        let reference = Context::with_params(ContextKind::Thermal, &[("temperature", 25.0)]);
+        let entity = |temperature: f64, intensity: f64| {
+            let ctx = Context::with_params(ContextKind::Thermal, &[("temperature", temperature)]);
+            let objective = Perspective::new(PerspectiveKind::Objective);
+            let mut e = OwnedEntity::new(ctx, Scale::Human, objective);
+            e.intensity = intensity;
+            e
+        };
+        let mut entities = vec![entity(60.0, 0.5), entity(30.0, 1.0), entity(25.5, 4.0)];
+        assert!(entities[2].rank_key(&reference) > entities[0].rank_key(&reference));
+
+        sort_by_relevance(&mut entities, &reference);
+        let order: Vec<f64> = entities.iter().map(|e| e.context.params["temperature"]).collect();
+        assert_eq!(order, [25.5, 30.0, 60.0]);
+    }
+}
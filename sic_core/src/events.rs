@@ -3,10 +3,22 @@
 //! Event-driven paradigm: no fixed-frequency loops. Events propagate
 //! through contexts that "resonate" based on activation conditions.
 
-use std::time::Instant;
+use crate::time::Instant;
+use core::sync::atomic::Ordering;
+use core::time::Duration;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Process-wide logical sequence for events created without a clock.
+#[cfg(target_has_atomic = "64")]
+static NEXT_TICK: core::sync::atomic::AtomicU64 = core::sync::atomic::AtomicU64::new(0);
+/// Without 64-bit atomics the sequence wraps after 2³² events
+#[cfg(not(target_has_atomic = "64"))]
+static NEXT_TICK: core::sync::atomic::AtomicU32 = core::sync::atomic::AtomicU32::new(0);
 
 /// Types of events that propagate through the context system.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EventKind {
     /// A sensor reading changed significantly
     SensorChange,
@@ -18,66 +30,245 @@ pub enum EventKind {
     PatternDetected,
     /// The environment assessment changed
     EnvironmentChange,
+    /// A propagation cascade settled; magnitude = events processed
+    CascadeComplete,
     /// Custom event type
     Custom(String),
 }
 
+impl EventKind {
+    /// Propagation priority (higher first): safe-mode and parameter
+    /// changes preempt queued movement and sensor traffic.
+    pub fn priority(&self) -> u8 {
+        match self {
+            EventKind::EnvironmentChange => 3,
+            EventKind::ParameterAdjust => 2,
+            EventKind::PatternDetected | EventKind::Movement => 1,
+            EventKind::SensorChange | EventKind::CascadeComplete | EventKind::Custom(_) => 0,
+        }
+    }
+}
+
+/// Typed data attached to an event.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventPayload {
+    #[default]
+    None,
+    /// A signed direction marker (e.g. widen 1 / narrow -1)
+    Direction(i8),
+    /// An error magnitude, kept at full precision
+    Error(f64),
+    /// An untyped integer, as carried by `Event::with_extra`
+    Raw(i32),
+}
+
+impl EventPayload {
+    /// The direction marker, or 0 for any other payload.
+    pub fn direction(&self) -> i8 {
+        match self {
+            EventPayload::Direction(d) => *d,
+            _ => 0,
+        }
+    }
+
+    /// The error magnitude, if the payload carries one.
+    pub fn error(&self) -> Option<f64> {
+        match self {
+            EventPayload::Error(e) => Some(*e),
+            _ => None,
+        }
+    }
+}
+
 /// An event that propagates through the context system.
 ///
 /// Events are value types (Clone + Send) so they can be freely
 /// passed between contexts without ownership issues.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Event {
     pub kind: EventKind,
     pub magnitude: f64,
+    /// With `serde`, stored as its age in seconds and rebased on
+    /// `Instant::now()` when loaded, so ages survive but epochs do not
+    #[cfg_attr(feature = "serde", serde(with = "crate::instant_serde"))]
     pub timestamp: Instant,
-    pub extra: i32,
+    /// Logical timestamp — a monotonic sequence number, or the reading of
+    /// the `LogicalClock` that created the event. Wall-clock independent.
+    pub tick: u64,
+    pub payload: EventPayload,
 }
 
 impl Event {
+    #[cfg(feature = "std")]
     pub fn new(kind: EventKind, magnitude: f64) -> Self {
-        Self {
-            kind,
-            magnitude,
-            timestamp: Instant::now(),
-            extra: 0,
-        }
+        Self::with_payload(kind, magnitude, EventPayload::None)
     }
 
+    /// Convenience for an integer payload (`EventPayload::Raw`).
+    #[cfg(feature = "std")]
     pub fn with_extra(kind: EventKind, magnitude: f64, extra: i32) -> Self {
+        Self::with_payload(kind, magnitude, EventPayload::Raw(extra))
+    }
+
+    #[cfg(feature = "std")]
+    pub fn with_payload(kind: EventKind, magnitude: f64, payload: EventPayload) -> Self {
+        Self::at_time(kind, magnitude, payload, Instant::now())
+    }
+
+    /// Create an event stamped with an explicit time and the next
+    /// sequence tick — the only way to create one without `std`.
+    pub fn at_time(
+        kind: EventKind,
+        magnitude: f64,
+        payload: EventPayload,
+        timestamp: Instant,
+    ) -> Self {
+        let tick = NEXT_TICK.fetch_add(1, Ordering::Relaxed);
+        #[allow(clippy::useless_conversion)]
+        Self::at(kind, magnitude, payload, timestamp, tick.into())
+    }
+
+    /// Create an event at an explicit logical tick.
+    #[cfg(feature = "std")]
+    pub fn at_tick(kind: EventKind, magnitude: f64, payload: EventPayload, tick: u64) -> Self {
+        Self::at(kind, magnitude, payload, Instant::now(), tick)
+    }
+
+    /// Create an event at an explicit time and logical tick.
+    pub fn at(
+        kind: EventKind,
+        magnitude: f64,
+        payload: EventPayload,
+        timestamp: Instant,
+        tick: u64,
+    ) -> Self {
         Self {
             kind,
             magnitude,
-            timestamp: Instant::now(),
-            extra,
+            timestamp,
+            tick,
+            payload,
         }
     }
 
     /// Time elapsed since the event was created (for decay calculations).
+    #[cfg(feature = "std")]
     pub fn age_secs(&self) -> f64 {
-        self.timestamp.elapsed().as_secs_f64()
+        self.age_secs_at(Instant::now())
+    }
+
+    /// Age measured against an explicit `now` (0 if `now` precedes it).
+    pub fn age_secs_at(&self, now: Instant) -> f64 {
+        now.saturating_duration_since(self.timestamp).as_secs_f64()
+    }
+
+    /// Logical age relative to the tick `now` (0 if `now` precedes the event).
+    pub fn age_ticks(&self, now: u64) -> u64 {
+        now.saturating_sub(self.tick)
+    }
+}
+
+/// A manual logical clock for deterministic event ordering and aging.
+///
+/// Events created through the clock carry its current reading as their
+/// `tick`, so replay and tests do not depend on the wall clock.
+#[derive(Debug, Clone, Default)]
+pub struct LogicalClock {
+    now: u64,
+}
+
+impl LogicalClock {
+    pub fn new() -> Self {
+        Self { now: 0 }
+    }
+
+    /// Current logical time.
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Advance the clock by `ticks`.
+    pub fn advance(&mut self, ticks: u64) {
+        self.now += ticks;
+    }
+
+    /// Create an event stamped with the current logical time.
+    #[cfg(feature = "std")]
+    pub fn event(&self, kind: EventKind, magnitude: f64) -> Event {
+        Event::at_tick(kind, magnitude, EventPayload::None, self.now)
+    }
+
+    /// `event` with an explicit wall-clock `timestamp`.
+    pub fn event_at(&self, kind: EventKind, magnitude: f64, timestamp: Instant) -> Event {
+        Event::at(kind, magnitude, EventPayload::None, timestamp, self.now)
+    }
+}
+
+/// What `EventQueue::enqueue` does when the queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Refuse the new event (the historical behavior)
+    #[default]
+    Reject,
+    /// Evict the oldest queued event to make room
+    DropOldest,
+    /// Double the buffer, keeping FIFO order
+    Grow,
+}
+
+/// Outcome of `EventQueue::enqueue`.
+#[derive(Debug, Clone)]
+pub enum EnqueueResult {
+    /// Stored without side effects
+    Enqueued,
+    /// Stored after evicting this oldest event
+    DroppedOldest(Event),
+    /// Stored after the buffer grew to `new_capacity`
+    Grew { new_capacity: usize },
+    /// Not stored; the event is handed back
+    Rejected(Event),
+}
+
+impl EnqueueResult {
+    /// True if the new event is now in the queue.
+    pub fn accepted(&self) -> bool {
+        !matches!(self, EnqueueResult::Rejected(_))
+    }
+
+    /// True if an event was lost — either the new one or an evicted one.
+    pub fn lost_event(&self) -> bool {
+        matches!(self, EnqueueResult::Rejected(_) | EnqueueResult::DroppedOldest(_))
     }
 }
 
 /// A circular event queue with fixed capacity (no allocations after init).
 ///
 /// Models the event queue from the Arduino implementation but generalized.
+/// Only `OverflowPolicy::Grow` ever reallocates.
 pub struct EventQueue {
     buffer: Vec<Option<Event>>,
     head: usize,
     tail: usize,
     count: usize,
     capacity: usize,
+    policy: OverflowPolicy,
 }
 
 impl EventQueue {
     pub fn new(capacity: usize) -> Self {
+        Self::with_policy(capacity, OverflowPolicy::Reject)
+    }
+
+    pub fn with_policy(capacity: usize, policy: OverflowPolicy) -> Self {
         Self {
             buffer: (0..capacity).map(|_| None).collect(),
             head: 0,
             tail: 0,
             count: 0,
             capacity,
+            policy,
         }
     }
 
@@ -89,15 +280,91 @@ impl EventQueue {
         self.count
     }
 
-    /// Enqueue an event. Returns false if the queue is full.
-    pub fn enqueue(&mut self, event: Event) -> bool {
-        if self.count >= self.capacity {
-            return false;
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Enqueue an event, applying the overflow policy if the queue is full.
+    pub fn enqueue(&mut self, event: Event) -> EnqueueResult {
+        if self.count < self.capacity {
+            self.push_back(event);
+            return EnqueueResult::Enqueued;
+        }
+        match self.policy {
+            OverflowPolicy::Reject => {
+                sic_warn!("event queue full ({}), dropping {:?}", self.capacity, event.kind);
+                EnqueueResult::Rejected(event)
+            }
+            OverflowPolicy::DropOldest => match self.dequeue() {
+                Some(oldest) => {
+                    sic_warn!("event queue full ({}), evicting {:?}", self.capacity, oldest.kind);
+                    self.push_back(event);
+                    EnqueueResult::DroppedOldest(oldest)
+                }
+                // Zero capacity: nothing to evict, nowhere to store
+                None => EnqueueResult::Rejected(event),
+            },
+            OverflowPolicy::Grow => {
+                self.grow();
+                self.push_back(event);
+                EnqueueResult::Grew {
+                    new_capacity: self.capacity,
+                }
+            }
         }
+    }
+
+    fn push_back(&mut self, event: Event) {
         self.buffer[self.tail] = Some(event);
         self.tail = (self.tail + 1) % self.capacity;
         self.count += 1;
-        true
+    }
+
+    /// Double the capacity, unrolling the ring so the oldest event sits at
+    /// index 0 — this handles the wrapped case (head ≥ tail) too.
+    fn grow(&mut self) {
+        let new_capacity = (self.capacity * 2).max(1);
+        let mut buffer: Vec<Option<Event>> = Vec::with_capacity(new_capacity);
+        for k in 0..self.count {
+            let idx = (self.head + k) % self.capacity;
+            buffer.push(self.buffer[idx].take());
+        }
+        buffer.resize_with(new_capacity, || None);
+        self.buffer = buffer;
+        self.head = 0;
+        self.tail = self.count % new_capacity;
+        self.capacity = new_capacity;
+    }
+
+    /// Dequeue the highest-priority event (see `EventKind::priority`);
+    /// ties go to the oldest, so equal priorities stay FIFO. Removal from
+    /// the middle shifts the younger events down — no allocation.
+    pub fn dequeue_highest(&mut self) -> Option<Event> {
+        let idx = |k: usize| (self.head + k) % self.capacity;
+        let mut best: Option<(usize, u8)> = None;
+        for k in 0..self.count {
+            let priority = self.buffer[idx(k)].as_ref().map_or(0, |e| e.kind.priority());
+            if best.is_none_or(|(_, p)| priority > p) {
+                best = Some((k, priority));
+            }
+        }
+        let (k, _) = best?;
+        if k == 0 {
+            return self.dequeue();
+        }
+
+        let event = self.buffer[idx(k)].take();
+        for m in k..self.count - 1 {
+            let next = self.buffer[idx(m + 1)].take();
+            self.buffer[idx(m)] = next;
+        }
+        self.tail = (self.tail + self.capacity - 1) % self.capacity;
+        self.count -= 1;
+        event
     }
 
     /// Dequeue the next event, if any.
@@ -111,3 +378,245 @@ impl EventQueue {
         event
     }
 }
+
+/// An event captured for replay, timed relative to the recording origin.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedEvent {
+    pub kind: EventKind,
+    pub magnitude: f64,
+    pub payload: EventPayload,
+    /// Time since the recording origin, kept exact to the nanosecond
+    pub offset: Duration,
+}
+
+impl RecordedEvent {
+    /// Capture `event` relative to `origin` (0 if it predates it).
+    pub fn capture(event: &Event, origin: Instant) -> Self {
+        Self {
+            kind: event.kind.clone(),
+            magnitude: event.magnitude,
+            payload: event.payload,
+            offset: event.timestamp.saturating_duration_since(origin),
+        }
+    }
+
+    /// Rebuild the event with its timestamp at `origin` + offset.
+    pub fn to_event(&self, origin: Instant) -> Event {
+        Event::at_time(self.kind.clone(), self.magnitude, self.payload, origin + self.offset)
+    }
+}
+
+/// Bounded-memory history of processed events (oldest evicted first).
+///
+/// Supports age-based queries over the recent stream without an
+/// unbounded log.
+pub struct EventHistory {
+    buffer: Vec<Option<Event>>,
+    head: usize,
+    count: usize,
+    capacity: usize,
+}
+
+impl EventHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            buffer: (0..capacity).map(|_| None).collect(),
+            head: 0,
+            count: 0,
+            capacity,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Record an event, evicting the oldest when full.
+    pub fn push(&mut self, event: Event) {
+        if self.capacity == 0 {
+            return;
+        }
+        let slot = (self.head + self.count) % self.capacity;
+        self.buffer[slot] = Some(event);
+        if self.count < self.capacity {
+            self.count += 1;
+        } else {
+            self.head = (self.head + 1) % self.capacity;
+        }
+    }
+
+    /// Recorded events from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &Event> {
+        (0..self.count).filter_map(move |i| self.buffer[(self.head + i) % self.capacity].as_ref())
+    }
+
+    /// Events of `kind` created within the last `secs` seconds.
+    #[cfg(feature = "std")]
+    pub fn count_in_window(&self, kind: &EventKind, secs: f64) -> usize {
+        self.count_in_window_at(kind, secs, Instant::now())
+    }
+
+    /// `count_in_window` measured against an explicit `now`.
+    pub fn count_in_window_at(&self, kind: &EventKind, secs: f64, now: Instant) -> usize {
+        self.iter()
+            .filter(|e| &e.kind == kind)
+            .filter(|e| now.saturating_duration_since(e.timestamp).as_secs_f64() <= secs)
+            .count()
+    }
+
+    /// Events of `kind` per second over the span the history covers
+    /// (oldest to newest recorded event). 0.0 if the span is empty.
+    pub fn rate(&self, kind: &EventKind) -> f64 {
+        let (first, last) = match (self.iter().next(), self.iter().last()) {
+            (Some(first), Some(last)) => (first.timestamp, last.timestamp),
+            _ => return 0.0,
+        };
+        let span = last.saturating_duration_since(first).as_secs_f64();
+        if span <= 0.0 {
+            return 0.0;
+        }
+        self.iter().filter(|e| &e.kind == kind).count() as f64 / span
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn origin() -> Instant {
+        #[cfg(feature = "std")]
+        return Instant::now();
+        #[cfg(not(feature = "std"))]
+        return Instant::from_millis(0);
+    }
+
+    #[test]
+    fn manual_clocks_give_predictable_ages() {
+        let t0 = origin();
+        let mut clock = LogicalClock::new();
+        let first = clock.event_at(EventKind::Movement, 1.0, t0);
+        clock.advance(3);
+        let second = clock.event_at(EventKind::Movement, 1.0, t0 + Duration::from_millis(250));
+        clock.advance(2);
+
+        assert_eq!((first.tick, second.tick), (0, 3));
+        assert_eq!(first.age_ticks(clock.now()), 5);
+        assert_eq!(second.age_ticks(clock.now()), 2);
+        assert_eq!(second.age_ticks(1), 0);
+
+        let now = t0 + Duration::from_secs(1);
+        assert_eq!(first.age_secs_at(now), 1.0);
+        assert_eq!(second.age_secs_at(now), 0.75);
+        assert_eq!(second.age_secs_at(t0), 0.0);
+    }
+
+    #[test]
+    fn history_rate_follows_the_injection_cadence() {
+        // one event every 100 ms, alternating movement and sensor
+        let t0 = origin();
+        let mut history = EventHistory::new(20);
+        let mut now = t0;
+        for i in 0..100u64 {
+            now = t0 + Duration::from_millis(100 * i);
+            let kind = if i % 2 == 0 { EventKind::Movement } else { EventKind::SensorChange };
+            history.push(Event::at(kind, 1.0, EventPayload::None, now, i));
+        }
+        assert_eq!(history.len(), 20);
+        assert_eq!(history.iter().next().unwrap().tick, 80);
+
+        // 10 movements over the 1.9 s the ring spans: ≈ 5 per second
+        let rate = history.rate(&EventKind::Movement);
+        assert!((rate - 10.0 / 1.9).abs() < 1e-9);
+        assert_eq!(history.count_in_window_at(&EventKind::Movement, 0.5, now), 3);
+    }
+
+    /// A logger keeping each test thread's warnings apart.
+    #[cfg(all(feature = "log", feature = "std"))]
+    mod capture {
+        use std::cell::RefCell;
+
+        thread_local! {
+            static WARNINGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+
+        struct Capture;
+
+        impl log::Log for Capture {
+            fn enabled(&self, metadata: &log::Metadata) -> bool {
+                metadata.level() <= log::Level::Warn
+            }
+
+            fn log(&self, record: &log::Record) {
+                if self.enabled(record.metadata()) {
+                    WARNINGS.with(|w| w.borrow_mut().push(record.args().to_string()));
+                }
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: Capture = Capture;
+
+        /// Warnings logged on this thread while `f` runs.
+        pub fn warnings(f: impl FnOnce()) -> Vec<String> {
+            // Fails harmlessly once another test has installed it
+            let _ = log::set_logger(&LOGGER);
+            log::set_max_level(log::LevelFilter::Warn);
+            WARNINGS.with(|w| w.borrow_mut().clear());
+            f();
+            WARNINGS.with(|w| w.take())
+        }
+    }
+
+    #[cfg(all(feature = "log", feature = "std"))]
+    #[test]
+    fn queue_overflow_logs_a_warning() {
+        let warnings = capture::warnings(|| {
+            let mut queue = EventQueue::new(1);
+            assert!(queue.enqueue(Event::new(EventKind::Movement, 1.0)).accepted());
+            assert!(!queue.enqueue(Event::new(EventKind::SensorChange, 2.0)).accepted());
+        });
+        assert_eq!(warnings, ["event queue full (1), dropping SensorChange"]);
+
+        let warnings = capture::warnings(|| {
+            let mut queue = EventQueue::with_policy(1, OverflowPolicy::DropOldest);
+            queue.enqueue(Event::new(EventKind::Movement, 1.0));
+            assert!(queue.enqueue(Event::new(EventKind::SensorChange, 2.0)).lost_event());
+        });
+        assert_eq!(warnings, ["event queue full (1), evicting Movement"]);
+    }
+
+    #[test]
+    fn queued_adjust_preempts_sensor_backlog() {
+        let t0 = origin();
+        let mut queue = EventQueue::new(4);
+        let event =
+            |kind, magnitude, tick| Event::at(kind, magnitude, EventPayload::None, t0, tick);
+        // wrap the ring so the backlog straddles the end of the buffer
+        queue.enqueue(event(EventKind::SensorChange, 0.0, 0));
+        queue.enqueue(event(EventKind::SensorChange, 0.0, 1));
+        queue.dequeue();
+        queue.dequeue();
+        for tick in 2..5 {
+            queue.enqueue(event(EventKind::SensorChange, 1.0, tick));
+        }
+        queue.enqueue(event(EventKind::ParameterAdjust, 0.2, 5));
+
+        let order: Vec<(EventKind, u64)> =
+            core::iter::from_fn(|| queue.dequeue_highest()).map(|e| (e.kind, e.tick)).collect();
+        assert_eq!(
+            order,
+            [
+                (EventKind::ParameterAdjust, 5),
+                (EventKind::SensorChange, 2),
+                (EventKind::SensorChange, 3),
+                (EventKind::SensorChange, 4),
+            ]
+        );
+    }
+}
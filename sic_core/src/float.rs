@@ -0,0 +1,34 @@
+//! `f64` functions missing from `core`, routed through `libm`
+//!
+//! Only compiled without `std`; importing `Float` there keeps the usual
+//! method syntax (`x.exp()`) working unchanged.
+
+pub(crate) trait Float {
+    fn exp(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn round(self) -> Self;
+    fn tanh(self) -> Self;
+}
+
+impl Float for f64 {
+    fn exp(self) -> f64 {
+        libm::exp(self)
+    }
+
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, f64::from(n))
+    }
+
+    fn round(self) -> f64 {
+        libm::round(self)
+    }
+
+    fn tanh(self) -> f64 {
+        libm::tanh(self)
+    }
+}
@@ -0,0 +1,48 @@
+//! `Instant` (de)serialization for the `serde` feature
+//!
+//! An `Instant` is opaque and process-local, so it is stored as its age
+//! in seconds at serialization time. Loading rebases the age on the
+//! current `Instant::now()`: relative timing between events is kept,
+//! while the time spent on disk is not counted. Ages that reach before
+//! the platform's earliest representable instant load as "now".
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::time::{Duration, Instant};
+
+fn age(instant: &Instant) -> f64 {
+    instant.elapsed().as_secs_f64()
+}
+
+fn rebase(age_secs: f64) -> Instant {
+    let now = Instant::now();
+    Duration::try_from_secs_f64(age_secs)
+        .ok()
+        .and_then(|age| now.checked_sub(age))
+        .unwrap_or(now)
+}
+
+pub fn serialize<S: Serializer>(instant: &Instant, serializer: S) -> Result<S::Ok, S::Error> {
+    age(instant).serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Instant, D::Error> {
+    f64::deserialize(deserializer).map(rebase)
+}
+
+/// The same encoding for `Option<Instant>`.
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        instant: &Option<Instant>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        instant.as_ref().map(age).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Instant>, D::Error> {
+        Ok(Option::<f64>::deserialize(deserializer)?.map(rebase))
+    }
+}
@@ -7,10 +7,87 @@
 //! - **Lifetimes** = Temporal decay (entities die when their context dies)
 //! - **Borrow checker** = Safe event propagation (no dangling references)
 //! - **Traits** = Context interfaces (activation conditions, processing)
+//!
+//! With the `log` feature, questionable situations (queue overflow,
+//! non-convergence, NaN parameters, clamp saturation) are reported through
+//! the `log` facade. Without it the diagnostics compile to nothing.
+//!
+//! The `serde` feature derives `Serialize`/`Deserialize` for the value
+//! types (contexts, scales, perspectives, entities, events, 𝕄).
+//!
+//! Without the default `std` feature the crate is `no_std` + `alloc` for
+//! embedded targets: `config`, `context`, `units`, `decay`, `events` and
+//! `nested_learning` remain, timed by caller-supplied milliseconds (see
+//! `time`), with `BTreeMap` parameters and `libm` math (enable `libm`).
+//! The coherence matrix, entities and operators need `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("sic_core without `std` needs the `libm` feature for its f64 math");
+
+/// `log::warn!` under the `log` feature; a no-op otherwise.
+macro_rules! sic_warn {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        log::warn!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    }};
+}
+
+/// `log::debug!` under the `log` feature; a no-op otherwise.
+macro_rules! sic_debug {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        log::debug!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    }};
+}
+
+/// Parameter maps: `HashMap` with `std`, `BTreeMap` without.
+#[cfg(feature = "std")]
+pub(crate) use std::collections::HashMap as Map;
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::collections::BTreeMap as Map;
+
+/// `alloc` items that the `std` prelude provides.
+#[cfg(not(feature = "std"))]
+mod prelude {
+    pub(crate) use alloc::boxed::Box;
+    pub(crate) use alloc::string::{String, ToString};
+    pub(crate) use alloc::vec;
+    pub(crate) use alloc::vec::Vec;
+}
+
+/// Test builds link `std`, whose inherent float methods take over
+#[cfg(all(not(feature = "std"), not(test)))]
+mod float;
 
+pub mod config;
 pub mod context;
+pub mod units;
+pub mod decay;
+#[cfg(feature = "std")]
 pub mod entity;
+#[cfg(feature = "std")]
 pub mod coherence;
+#[cfg(feature = "std")]
+pub mod sparse;
+#[cfg(feature = "std")]
 pub mod operators;
 pub mod events;
 pub mod nested_learning;
+pub mod time;
+#[cfg(feature = "testing")]
+pub mod scenarios;
+#[cfg(feature = "serde")]
+mod instant_serde;
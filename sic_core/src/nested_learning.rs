@@ -11,8 +11,20 @@
 //! Event-driven: no polling. Contexts "resonate" with events based on
 //! activation conditions. Memory decays exponentially with real time.
 
-use crate::events::{Event, EventKind, EventQueue};
-use std::time::Instant;
+use crate::config::SicConfig;
+use crate::decay::ExponentialDecay;
+use crate::events::{
+    Event, EventHistory, EventKind, EventPayload, EventQueue, OverflowPolicy, RecordedEvent,
+};
+use crate::time::Instant;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::float::Float;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+#[cfg(not(feature = "std"))]
+use alloc::collections::VecDeque;
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
 
 /// Trait for any context processor in the nested learning system.
 ///
@@ -28,50 +40,360 @@ pub trait ContextProcessor {
     /// Apply temporal decay based on elapsed time.
     /// decay(Δt) = value × exp(-Δt/τ)
     fn decay(&mut self, delta_t_secs: f64);
+
+    /// Refractory period in seconds: after processing an event, the
+    /// system ignores re-activations whose timestamps fall within it.
+    fn activation_hysteresis(&self) -> f64 {
+        0.0
+    }
+
+    /// Label passed to event listeners for this context.
+    fn name(&self) -> &str {
+        "processor"
+    }
+}
+
+/// Activation predicate of an `FnContext`.
+pub type ActivateFn = Box<dyn Fn(&Event) -> bool>;
+/// Processing closure of an `FnContext`.
+pub type ProcessFn = Box<dyn FnMut(&Event) -> Option<Event>>;
+
+/// Closure-based context for quick prototyping.
+///
+/// Activates on a custom predicate and runs a custom closure, without a
+/// dedicated struct implementing `ContextProcessor`. Decay is a no-op.
+pub struct FnContext {
+    pub activate: ActivateFn,
+    pub process: ProcessFn,
+}
+
+impl FnContext {
+    pub fn new(
+        activate: impl Fn(&Event) -> bool + 'static,
+        process: impl FnMut(&Event) -> Option<Event> + 'static,
+    ) -> Self {
+        Self {
+            activate: Box::new(activate),
+            process: Box::new(process),
+        }
+    }
+}
+
+impl ContextProcessor for FnContext {
+    fn should_activate(&self, event: &Event) -> bool {
+        (self.activate)(event)
+    }
+
+    fn process(&mut self, event: &Event) -> Option<Event> {
+        (self.process)(event)
+    }
+
+    fn decay(&mut self, _delta_t_secs: f64) {
+        // Closure contexts carry no decaying memory
+    }
+}
+
+/// Shape applied to the normalized error e = error / full_scale before
+/// scaling by the gain. All curves are odd and map e = ±1 to ±1.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ResponseCurve {
+    /// r(e) = e — proportional
+    #[default]
+    Linear,
+    /// r(e) = e·|e| — gentle near center, aggressive near extremes
+    Quadratic,
+    /// r(e) = tanh(2e) / tanh(2) — aggressive near center, saturating
+    Tanh,
+}
+
+impl ResponseCurve {
+    pub fn apply(&self, e: f64) -> f64 {
+        match self {
+            ResponseCurve::Linear => e,
+            ResponseCurve::Quadratic => e * e.abs(),
+            ResponseCurve::Tanh => (2.0 * e).tanh() / 2.0_f64.tanh(),
+        }
+    }
+}
+
+/// Signal differentiated by the reactive derivative term.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DerivativeMode {
+    /// d(error)/dt — kicks when the setpoint jumps
+    OnError,
+    /// d(measurement)/dt — immune to setpoint steps
+    #[default]
+    OnMeasurement,
 }
 
 /// Reactive Context — direct sensor→actuator responses.
+///
+/// Closes the loop with the adaptive context by consuming `ParameterAdjust`
+/// events. Gain changes pass through a hysteresis band so alternating
+/// ±adjustments cannot make the gain chatter.
 pub struct ReactiveContext {
     pub gain: f64,
     pub dead_zone: f64,
     pub position: f64,
+    /// Adjustments smaller than this are ignored as noise.
+    pub min_gain_step: f64,
+    /// Cumulative change required before an adjustment is applied.
+    pub gain_band: f64,
+    /// Sensor error mapped to a normalized error of 1.0
+    pub sensor_full_scale: f64,
+    /// Step for a full-scale error at unit gain
+    pub max_step: f64,
+    /// Response curve applied to the normalized error
+    pub response: ResponseCurve,
+    /// Conservative gain adopted when the environment signals safe mode
+    pub safe_gain: f64,
+    /// Refractory period after processing an event, in seconds
+    pub refractory: f64,
+    /// Reference the sensor value is compared against: error = value − setpoint
+    pub setpoint: f64,
+    /// Derivative gain; 0 disables the derivative term
+    pub derivative_gain: f64,
+    /// Which signal the derivative term differentiates
+    pub derivative_mode: DerivativeMode,
+    /// Accumulated sub-dead-zone error that triggers a corrective nudge;
+    /// 0 disables the slow integral
+    pub integral_threshold: f64,
+    /// Position nudge applied when the integral fires, in degrees
+    pub integral_step: f64,
+    integral: f64,
+    pending_gain: f64,
+    /// (measurement, error, timestamp) of the previous sensor event
+    previous: Option<(f64, f64, Instant)>,
+}
+
+impl Default for ReactiveContext {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ReactiveContext {
     pub fn new() -> Self {
+        Self::with_config(&SicConfig::default())
+    }
+
+    pub fn with_config(config: &SicConfig) -> Self {
         Self {
             gain: 0.5,
             dead_zone: 30.0,
             position: 90.0, // center
+            min_gain_step: 0.05,
+            gain_band: 0.2,
+            sensor_full_scale: config.sensor_full_scale,
+            max_step: config.max_step,
+            response: ResponseCurve::Linear,
+            safe_gain: 0.1,
+            refractory: 0.0,
+            setpoint: 0.0,
+            derivative_gain: 0.0,
+            derivative_mode: DerivativeMode::OnMeasurement,
+            integral_threshold: 0.0,
+            integral_step: 1.0,
+            integral: 0.0,
+            pending_gain: 0.0,
+            previous: None,
+        }
+    }
+
+    /// Move by `requested` degrees within [0, 180] and report the movement,
+    /// stamped `at` the triggering event's time.
+    fn move_by(&mut self, requested: f64, error: f64, at: Instant) -> Option<Event> {
+        let target = (self.position + requested).clamp(0.0, 180.0);
+        if target != self.position + requested {
+            sic_debug!("position {} saturated at {}", self.position + requested, target);
+        }
+
+        // Only the movement that survives the clamp is real
+        let delta = target - self.position;
+        self.position = target;
+        if delta == 0.0 {
+            return None;
         }
+
+        Some(Event::at_time(
+            EventKind::Movement,
+            delta.abs(),
+            EventPayload::Error(error.abs()),
+            at,
+        ))
+    }
+
+    /// Rate of change of the differentiated signal since the previous
+    /// sensor event, using event timestamps for Δt.
+    fn derivative(&mut self, measurement: f64, error: f64, at: Instant) -> f64 {
+        let rate = match self.previous {
+            Some((m, e, t)) => {
+                let dt = at.saturating_duration_since(t).as_secs_f64();
+                if dt <= 0.0 {
+                    0.0
+                } else {
+                    match self.derivative_mode {
+                        DerivativeMode::OnError => (error - e) / dt,
+                        DerivativeMode::OnMeasurement => (measurement - m) / dt,
+                    }
+                }
+            }
+            None => 0.0,
+        };
+        self.previous = Some((measurement, error, at));
+        rate
+    }
+
+    /// Accumulate a gain adjustment and apply it once it leaves the band.
+    fn adjust_gain(&mut self, change: f64) {
+        if change.abs() < self.min_gain_step {
+            return;
+        }
+        self.pending_gain += change;
+        if self.pending_gain.abs() >= self.gain_band {
+            let requested = self.gain + self.pending_gain;
+            self.gain = requested.clamp(0.05, 2.0);
+            if self.gain != requested {
+                sic_debug!("gain {} saturated at {}", requested, self.gain);
+            }
+            self.pending_gain = 0.0;
+        }
+    }
+
+    /// Gain change accumulated inside the band, not yet applied.
+    pub fn pending_gain(&self) -> f64 {
+        self.pending_gain
+    }
+
+    /// Integral of in-dead-zone error towards the next nudge.
+    pub fn integral(&self) -> f64 {
+        self.integral
     }
 }
 
 impl ContextProcessor for ReactiveContext {
     fn should_activate(&self, event: &Event) -> bool {
-        event.kind == EventKind::SensorChange
+        match event.kind {
+            EventKind::SensorChange | EventKind::ParameterAdjust => true,
+            EventKind::EnvironmentChange => event.payload.direction() == SAFE_MODE,
+            _ => false,
+        }
     }
 
     fn process(&mut self, event: &Event) -> Option<Event> {
-        let error = event.magnitude;
-        if error.abs() <= self.dead_zone {
-            return None;
+        match event.kind {
+            EventKind::ParameterAdjust => {
+                self.adjust_gain(event.magnitude);
+                return None;
+            }
+            EventKind::EnvironmentChange => {
+                // Safe mode: snap to the conservative preset
+                self.gain = self.safe_gain;
+                self.pending_gain = 0.0;
+                return None;
+            }
+            _ => {}
         }
 
-        // Proportional movement
-        let delta = self.gain * (error / 512.0) * 10.0;
-        self.position = (self.position + delta).clamp(0.0, 180.0);
+        let error = event.magnitude - self.setpoint;
+        let rate = self.derivative(event.magnitude, error, event.timestamp);
+        if error.abs() <= self.dead_zone {
+            // Slow integral: a persistent small bias eventually moves us
+            if self.integral_threshold <= 0.0 {
+                return None;
+            }
+            self.integral += error;
+            if self.integral.abs() < self.integral_threshold {
+                return None;
+            }
+            let nudge = self.integral.signum() * self.integral_step;
+            self.integral = 0.0;
+            return self.move_by(nudge, error, event.timestamp);
+        }
+        self.integral = 0.0;
 
-        Some(Event::with_extra(
-            EventKind::Movement,
-            delta.abs(),
-            error.abs() as i32,
-        ))
+        // Shaped proportional movement plus the derivative term
+        let shaped = self.response.apply(error / self.sensor_full_scale);
+        let d_term = self.derivative_gain * rate / self.sensor_full_scale;
+        let requested = (self.gain * shaped + d_term) * self.max_step;
+        self.move_by(requested, error, event.timestamp)
     }
 
     fn decay(&mut self, _delta_t_secs: f64) {
         // Reactive context has no memory to decay
     }
+
+    fn activation_hysteresis(&self) -> f64 {
+        self.refractory
+    }
+
+    fn name(&self) -> &str {
+        "reactive"
+    }
+}
+
+/// One side of an adaptation-rule condition.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Threshold {
+    /// Holds when the value is strictly above the bound
+    Above(f64),
+    /// Holds when the value is strictly below the bound
+    Below(f64),
+}
+
+impl Threshold {
+    pub fn holds(&self, value: f64) -> bool {
+        match *self {
+            Threshold::Above(bound) => value > bound,
+            Threshold::Below(bound) => value < bound,
+        }
+    }
+
+    /// The bound, regardless of direction.
+    pub fn bound_mut(&mut self) -> &mut f64 {
+        match self {
+            Threshold::Above(bound) | Threshold::Below(bound) => bound,
+        }
+    }
+}
+
+/// An adaptation rule (Section 16.3): when both the movement and error
+/// averages satisfy their thresholds, propose `gain_change`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptationRule {
+    pub movement: Threshold,
+    pub error: Threshold,
+    pub gain_change: f64,
+}
+
+impl AdaptationRule {
+    pub fn matches(&self, movement_avg: f64, error_avg: f64) -> bool {
+        self.movement.holds(movement_avg) && self.error.holds(error_avg)
+    }
+
+    /// The three default rules, in evaluation order.
+    pub fn defaults() -> Vec<AdaptationRule> {
+        vec![
+            // RULE 1: High movement + high error → reduce gain (nervous system)
+            AdaptationRule {
+                movement: Threshold::Above(0.6),
+                error: Threshold::Above(0.1),
+                gain_change: -0.15,
+            },
+            // RULE 2: Low movement + high error → increase gain (slow system)
+            AdaptationRule {
+                movement: Threshold::Below(0.2),
+                error: Threshold::Above(0.2),
+                gain_change: 0.15,
+            },
+            // RULE 3: High movement + low error → reduce unnecessary gain
+            AdaptationRule {
+                movement: Threshold::Above(0.8),
+                error: Threshold::Below(0.06),
+                gain_change: -0.10,
+            },
+        ]
+    }
 }
 
 /// Adaptive Context — detects patterns and adjusts reactive parameters.
@@ -81,20 +403,81 @@ pub struct AdaptiveContext {
     pub movement_avg: f64,
     pub energy_threshold: f64,
     pub tau: f64, // time constant in seconds
-    last_update: Instant,
+    /// Sensor error mapped to a normalized error of 1.0
+    pub sensor_full_scale: f64,
+    /// Movement mapped to a normalized movement of 1.0
+    pub movement_full_scale: f64,
+    /// Refractory period after processing an event, in seconds
+    pub refractory: f64,
+    /// Reference the sensor value is compared against, as in the reactive
+    /// context; keep the two equal (see `NestedLearningSystem::set_setpoint`)
+    pub setpoint: f64,
+    /// Sensor errors averaged so far; the first one seeds `error_avg`
+    error_samples: u64,
+    last_update: Option<Instant>,
+    last_rule: Option<(usize, Instant)>,
+    rules: Vec<AdaptationRule>,
+}
+
+impl Default for AdaptiveContext {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AdaptiveContext {
     pub fn new() -> Self {
+        Self::with_config(&SicConfig::default())
+    }
+
+    pub fn with_config(config: &SicConfig) -> Self {
         Self {
             accumulated_energy: 0.0,
             error_avg: 0.0,
             movement_avg: 0.0,
             energy_threshold: 500.0,
             tau: 0.2, // 200ms
-            last_update: Instant::now(),
+            sensor_full_scale: config.sensor_full_scale,
+            movement_full_scale: config.movement_full_scale,
+            refractory: 0.0,
+            setpoint: 0.0,
+            error_samples: 0,
+            last_update: None,
+            last_rule: None,
+            rules: AdaptationRule::defaults(),
         }
     }
+
+    /// The adaptation rule that last decided a gain change and its age in
+    /// seconds. Rules are indexed from 0 in evaluation order (RULE 1 → 0).
+    #[cfg(feature = "std")]
+    pub fn last_rule_fired(&self) -> Option<(usize, f64)> {
+        self.last_rule_fired_at(Instant::now())
+    }
+
+    /// `last_rule_fired` with the age measured against an explicit `now`,
+    /// e.g. `last_update()` for the event clock during a replay.
+    pub fn last_rule_fired_at(&self, now: Instant) -> Option<(usize, f64)> {
+        self.last_rule
+            .map(|(rule, at)| (rule, now.saturating_duration_since(at).as_secs_f64()))
+    }
+
+    /// The adaptation rules in evaluation order. When several match, the
+    /// last one wins.
+    pub fn rules(&self) -> &[AdaptationRule] {
+        &self.rules
+    }
+
+    /// Mutable access to the rules, for tuning thresholds in place.
+    pub fn rules_mut(&mut self) -> &mut Vec<AdaptationRule> {
+        &mut self.rules
+    }
+
+    /// Time of the last energy update, the adaptive clock (None before
+    /// the first event).
+    pub fn last_update(&self) -> Option<Instant> {
+        self.last_update
+    }
 }
 
 impl ContextProcessor for AdaptiveContext {
@@ -108,19 +491,28 @@ impl ContextProcessor for AdaptiveContext {
     fn process(&mut self, event: &Event) -> Option<Event> {
         self.accumulated_energy += event.magnitude.abs();
 
-        // Time-aware exponential moving average
-        let dt = self.last_update.elapsed().as_secs_f64();
-        let alpha = (1.0 - (-dt / self.tau).exp()).clamp(0.02, 0.5);
-        self.last_update = Instant::now();
+        // Time-aware exponential moving average, clocked by the event
+        // timestamps so a replayed stream sees the same Δt
+        let dt = self.last_update.map_or(0.0, |last| {
+            event.timestamp.saturating_duration_since(last).as_secs_f64()
+        });
+        let alpha = (1.0 - ExponentialDecay::new(self.tau).factor(dt)).clamp(0.02, 0.5);
+        self.last_update = Some(event.timestamp);
 
         match event.kind {
             EventKind::Movement => {
-                let normalized = (event.magnitude.abs() / 5.0).min(1.0);
+                let normalized = (event.magnitude.abs() / self.movement_full_scale).min(1.0);
                 self.movement_avg = self.movement_avg * (1.0 - alpha) + normalized * alpha;
             }
             EventKind::SensorChange => {
-                let normalized = (event.magnitude.abs() / 512.0).min(1.0);
-                self.error_avg = self.error_avg * (1.0 - alpha) + normalized * alpha;
+                let error = event.magnitude - self.setpoint;
+                let normalized = (error.abs() / self.sensor_full_scale).min(1.0);
+                self.error_avg = if self.error_samples == 0 {
+                    normalized
+                } else {
+                    self.error_avg * (1.0 - alpha) + normalized * alpha
+                };
+                self.error_samples += 1;
             }
             _ => {}
         }
@@ -131,28 +523,27 @@ impl ContextProcessor for AdaptiveContext {
 
         // Adaptation rules (Section 16.3)
         let mut gain_change: f64 = 0.0;
+        let mut fired = None;
 
-        // RULE 1: High movement + high error → reduce gain (nervous system)
-        if self.movement_avg > 0.6 && self.error_avg > 0.1 {
-            gain_change = -0.15;
-        }
-        // RULE 2: Low movement + high error → increase gain (slow system)
-        if self.movement_avg < 0.2 && self.error_avg > 0.2 {
-            gain_change = 0.15;
-        }
-        // RULE 3: High movement + low error → reduce unnecessary gain
-        if self.movement_avg > 0.8 && self.error_avg < 0.06 {
-            gain_change = -0.10;
+        for (idx, rule) in self.rules.iter().enumerate() {
+            if rule.matches(self.movement_avg, self.error_avg) {
+                gain_change = rule.gain_change;
+                fired = Some(idx);
+            }
         }
 
         self.accumulated_energy = 0.0;
+        if let Some(rule) = fired {
+            self.last_rule = Some((rule, event.timestamp));
+        }
 
         if gain_change.abs() > 0.01 {
             let direction = if gain_change > 0.0 { 1 } else { -1 };
-            Some(Event::with_extra(
+            Some(Event::at_time(
                 EventKind::ParameterAdjust,
                 gain_change,
-                direction,
+                EventPayload::Direction(direction),
+                event.timestamp,
             ))
         } else {
             None
@@ -160,22 +551,51 @@ impl ContextProcessor for AdaptiveContext {
     }
 
     fn decay(&mut self, delta_t_secs: f64) {
-        let factor = (-delta_t_secs / self.tau).exp();
-        self.accumulated_energy *= factor;
+        let fast = ExponentialDecay::new(self.tau);
+        self.accumulated_energy = fast.apply(self.accumulated_energy, delta_t_secs);
 
-        let slow_factor = (-delta_t_secs / (self.tau * 10.0)).exp();
-        self.movement_avg *= slow_factor;
-        self.error_avg *= slow_factor;
+        let slow = ExponentialDecay::new(self.tau * 10.0);
+        self.movement_avg = slow.apply(self.movement_avg, delta_t_secs);
+        self.error_avg = slow.apply(self.error_avg, delta_t_secs);
+    }
+
+    fn activation_hysteresis(&self) -> f64 {
+        self.refractory
+    }
+
+    fn name(&self) -> &str {
+        "adaptive"
     }
 }
 
+/// Direction marker of the `EnvironmentChange` that puts the reactive
+/// context into safe mode (widen = 1, narrow = -1).
+pub const SAFE_MODE: i8 = 2;
+
 /// Environmental Context — assesses global performance and adjusts adaptive limits.
 pub struct EnvironmentalContext {
     pub samples: usize,
     pub sample_threshold: usize,
     pub adjustments: usize,
     pub oscillations: usize,
-    last_direction: i32,
+    /// Oscillation ratio above which an assessment counts as runaway
+    pub safe_osc_threshold: f64,
+    /// Consecutive runaway assessments that trigger safe mode
+    pub safe_trigger_count: usize,
+    /// Oscillation ratio above which allowed ranges widen
+    pub widen_osc_threshold: f64,
+    /// Oscillation ratio below which allowed ranges narrow
+    pub narrow_osc_threshold: f64,
+    /// Refractory period after processing an event, in seconds
+    pub refractory: f64,
+    last_direction: i8,
+    consecutive_runaway: usize,
+}
+
+impl Default for EnvironmentalContext {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl EnvironmentalContext {
@@ -185,9 +605,30 @@ impl EnvironmentalContext {
             sample_threshold: 50,
             adjustments: 0,
             oscillations: 0,
+            safe_osc_threshold: 0.8,
+            safe_trigger_count: 3,
+            widen_osc_threshold: 0.5,
+            narrow_osc_threshold: 0.2,
+            refractory: 0.0,
             last_direction: 0,
+            consecutive_runaway: 0,
         }
     }
+
+    /// Oscillations per adjustment in the current assessment window.
+    pub fn oscillation_ratio(&self) -> f64 {
+        self.oscillations as f64 / self.adjustments.max(1) as f64
+    }
+
+    /// Direction of the last parameter adjustment (1, -1, or 0 if none).
+    pub fn last_direction(&self) -> i8 {
+        self.last_direction
+    }
+
+    /// Runaway assessments in a row, towards `safe_trigger_count`.
+    pub fn consecutive_runaway(&self) -> usize {
+        self.consecutive_runaway
+    }
 }
 
 impl ContextProcessor for EnvironmentalContext {
@@ -200,7 +641,7 @@ impl ContextProcessor for EnvironmentalContext {
         self.samples += 1;
 
         // Detect oscillations
-        let dir = event.extra;
+        let dir = event.payload.direction();
         if dir != 0 && dir != self.last_direction && self.last_direction != 0 {
             self.oscillations += 1;
         }
@@ -210,21 +651,38 @@ impl ContextProcessor for EnvironmentalContext {
             return None;
         }
 
-        let osc_ratio = self.oscillations as f64 / self.adjustments.max(1) as f64;
+        let osc_ratio = self.oscillation_ratio();
+
+        if osc_ratio > self.safe_osc_threshold {
+            self.consecutive_runaway += 1;
+        } else {
+            self.consecutive_runaway = 0;
+        }
 
-        let result = if osc_ratio > 0.5 {
+        let result = if self.consecutive_runaway >= self.safe_trigger_count {
+            // Sustained runaway → fall back to the conservative preset
+            self.consecutive_runaway = 0;
+            Some(Event::at_time(
+                EventKind::EnvironmentChange,
+                osc_ratio,
+                EventPayload::Direction(SAFE_MODE),
+                event.timestamp,
+            ))
+        } else if osc_ratio > self.widen_osc_threshold {
             // Oscillating → widen allowed ranges
-            Some(Event::with_extra(
+            Some(Event::at_time(
                 EventKind::EnvironmentChange,
                 osc_ratio,
-                1, // widen
+                EventPayload::Direction(1), // widen
+                event.timestamp,
             ))
-        } else if osc_ratio < 0.2 && self.adjustments > 5 {
+        } else if osc_ratio < self.narrow_osc_threshold && self.adjustments > 5 {
             // Converging → narrow ranges for precision
-            Some(Event::with_extra(
+            Some(Event::at_time(
                 EventKind::EnvironmentChange,
                 osc_ratio,
-                -1, // narrow
+                EventPayload::Direction(-1), // narrow
+                event.timestamp,
             ))
         } else {
             None
@@ -241,67 +699,1473 @@ impl ContextProcessor for EnvironmentalContext {
     fn decay(&mut self, _delta_t_secs: f64) {
         // Environmental context has long-term memory, minimal decay
     }
+
+    fn activation_hysteresis(&self) -> f64 {
+        self.refractory
+    }
+
+    fn name(&self) -> &str {
+        "environmental"
+    }
+}
+
+/// The learned/tunable parameters of the reactive and adaptive contexts,
+/// swapped in as a unit so a live update never leaves them half-applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterSet {
+    pub gain: f64,
+    pub dead_zone: f64,
+    pub min_gain_step: f64,
+    pub gain_band: f64,
+    pub response: ResponseCurve,
+    pub energy_threshold: f64,
+    pub tau: f64,
+}
+
+/// System state after one sensor sample, for offline analysis.
+#[derive(Debug, Clone)]
+pub struct StepRecord {
+    pub sensor_value: f64,
+    pub position: f64,
+    pub gain: f64,
+    pub error_avg: f64,
+    pub movement_avg: f64,
+    /// Adaptive accumulated energy
+    pub energy: f64,
+    /// Events processed while the sample's cascade ran
+    pub events: Vec<Event>,
+}
+
+/// Reactive output after a cascade that changed it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutputSample {
+    pub position: f64,
+    pub gain: f64,
+    pub t: Instant,
+}
+
+/// Point-in-time view of the whole learning system, for dashboards.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SystemSnapshot {
+    pub gain: f64,
+    pub position: f64,
+    pub dead_zone: f64,
+    pub pending_gain: f64,
+    pub accumulated_energy: f64,
+    pub error_avg: f64,
+    pub movement_avg: f64,
+    /// Oscillations per adjustment in the current assessment window
+    pub oscillation_ratio: f64,
+    pub adjustments: usize,
+    pub samples: usize,
+    /// Direction of the last parameter adjustment (1, -1, or 0 if none)
+    pub last_direction: i8,
+    /// Runaway assessments in a row, towards safe mode
+    pub consecutive_runaway: usize,
+    pub frozen: bool,
+    /// Events waiting in the queue
+    pub queue_depth: usize,
+    pub lost_events: usize,
+}
+
+/// Why a propagation cascade stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    /// The queue drained on its own — the normal path
+    Quiescent,
+    /// The same sequence of events kept repeating; the rest was dropped
+    CycleDetected,
+    /// `MAX_CASCADE_EVENTS` were processed without the queue draining
+    IterationCap,
+}
+
+/// Result of propagating one sensor event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropagationOutcome {
+    pub events_processed: usize,
+    pub terminated_by: Reason,
+}
+
+/// Backstop on events processed per cascade, behind cycle detection.
+pub const MAX_CASCADE_EVENTS: usize = 10_000;
+
+/// Signatures of recent events kept for cycle detection.
+const CYCLE_WINDOW: usize = 48;
+/// Back-to-back repetitions of a pattern that count as a cycle.
+const CYCLE_REPEATS: usize = 3;
+
+/// Bounded history of (kind, quantized magnitude, payload) signatures.
+///
+/// A cycle is a pattern of period p ≤ `CYCLE_WINDOW / CYCLE_REPEATS`
+/// seen `CYCLE_REPEATS` times in a row at the end of the history.
+/// Magnitudes are compared at 1e-9 resolution, so a converging or
+/// diverging sequence is not mistaken for a loop.
+struct CycleDetector {
+    recent: VecDeque<(EventKind, i64, EventPayload)>,
+}
+
+impl CycleDetector {
+    fn new() -> Self {
+        Self {
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Record `event`; true once the tail of the history is periodic.
+    fn observe(&mut self, event: &Event) -> bool {
+        if self.recent.len() == CYCLE_WINDOW {
+            self.recent.pop_front();
+        }
+        let magnitude = (event.magnitude * 1e9).round() as i64;
+        self.recent.push_back((event.kind.clone(), magnitude, event.payload));
+
+        let len = self.recent.len();
+        (1..=len / CYCLE_REPEATS).any(|period| {
+            (0..period * (CYCLE_REPEATS - 1))
+                .all(|i| self.recent[len - 1 - i] == self.recent[len - 1 - i - period])
+        })
+    }
+}
+
+/// Callback receiving `OutputSample`s.
+pub type OutputFn = Box<dyn FnMut(OutputSample)>;
+
+/// Callback receiving each event with the name of the context (or
+/// `"sensor"`/`"queue"`) that produced or consumed it.
+pub type EventListener = Box<dyn FnMut(&Event, &str)>;
+
+/// Which layers of the nested system take part in propagation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layers {
+    pub reactive: bool,
+    pub adaptive: bool,
+    pub environmental: bool,
+}
+
+impl Default for Layers {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl Layers {
+    pub const ALL: Layers = Layers {
+        reactive: true,
+        adaptive: true,
+        environmental: true,
+    };
+    /// Two-tier setup: reactive + adaptive, no environmental oversight.
+    pub const TWO_TIER: Layers = Layers {
+        reactive: true,
+        adaptive: true,
+        environmental: false,
+    };
 }
 
 /// The complete Nested Learning System.
 ///
-/// Orchestrates three contexts with event-driven propagation.
+/// Orchestrates three contexts with event-driven propagation. A disabled
+/// layer keeps its struct (and its parameters) but never activates or
+/// decays. Further timescales plug in through `register`.
 pub struct NestedLearningSystem {
     pub reactive: ReactiveContext,
     pub adaptive: AdaptiveContext,
     pub environmental: EnvironmentalContext,
+    pub layers: Layers,
     pub event_queue: EventQueue,
-    last_decay: Instant,
+    /// Optional bounded record of every processed event
+    pub history: Option<EventHistory>,
+    /// (lowest error_avg seen, gain at that moment)
+    best: Option<(f64, f64)>,
+    /// `adaptive.error_samples` at the last checkpoint
+    best_checked: u64,
+    output: Option<OutputFn>,
+    listeners: Vec<EventListener>,
+    frozen: bool,
+    /// Events lost to queue overflow since construction
+    lost_events: usize,
+    /// Timestamp of the last event each layer processed
+    /// (reactive, adaptive, environmental), for refractory periods
+    last_fired: [Option<Instant>; 3],
+    /// Registered processors with the timestamp of their last event,
+    /// evaluated after the three built-in layers in registration order
+    processors: Vec<(Box<dyn ContextProcessor>, Option<Instant>)>,
+    /// Recording origin and the events captured since
+    recording: Option<(Instant, Vec<RecordedEvent>)>,
+    /// Time decay was last applied (None before the first cascade)
+    last_decay: Option<Instant>,
+}
+
+impl Default for NestedLearningSystem {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl NestedLearningSystem {
     pub fn new() -> Self {
+        Self::with_config(&SicConfig::default())
+    }
+
+    /// Chainable builder for tuning thresholds before construction.
+    pub fn builder() -> NestedLearningBuilder {
+        NestedLearningBuilder::new()
+    }
+
+    /// Build the system with custom normalization constants.
+    pub fn with_config(config: &SicConfig) -> Self {
+        Self::with_layers(config, Layers::ALL)
+    }
+
+    /// Build the system with only the given layers taking part.
+    pub fn with_layers(config: &SicConfig, layers: Layers) -> Self {
         Self {
-            reactive: ReactiveContext::new(),
-            adaptive: AdaptiveContext::new(),
+            reactive: ReactiveContext::with_config(config),
+            adaptive: AdaptiveContext::with_config(config),
             environmental: EnvironmentalContext::new(),
+            layers,
             event_queue: EventQueue::new(32),
-            last_decay: Instant::now(),
+            history: None,
+            best: None,
+            best_checked: 0,
+            output: None,
+            listeners: Vec::new(),
+            frozen: false,
+            last_fired: [None; 3],
+            processors: Vec::new(),
+            recording: None,
+            lost_events: 0,
+            last_decay: None,
+        }
+    }
+
+    /// Current tunables of the reactive and adaptive contexts.
+    pub fn parameters(&self) -> ParameterSet {
+        ParameterSet {
+            gain: self.reactive.gain,
+            dead_zone: self.reactive.dead_zone,
+            min_gain_step: self.reactive.min_gain_step,
+            gain_band: self.reactive.gain_band,
+            response: self.reactive.response,
+            energy_threshold: self.adaptive.energy_threshold,
+            tau: self.adaptive.tau,
+        }
+    }
+
+    /// Copy of the current state of all three contexts and the queue.
+    ///
+    /// Allocation-free, so it can be taken on every sample.
+    pub fn snapshot(&self) -> SystemSnapshot {
+        SystemSnapshot {
+            gain: self.reactive.gain,
+            position: self.reactive.position,
+            dead_zone: self.reactive.dead_zone,
+            pending_gain: self.reactive.pending_gain,
+            accumulated_energy: self.adaptive.accumulated_energy,
+            error_avg: self.adaptive.error_avg,
+            movement_avg: self.adaptive.movement_avg,
+            oscillation_ratio: self.environmental.oscillation_ratio(),
+            adjustments: self.environmental.adjustments,
+            samples: self.environmental.samples,
+            last_direction: self.environmental.last_direction,
+            consecutive_runaway: self.environmental.consecutive_runaway,
+            frozen: self.frozen,
+            queue_depth: self.event_queue.len(),
+            lost_events: self.lost_events,
+        }
+    }
+
+    /// Swap in a complete parameter set at once.
+    ///
+    /// Takes `&mut self`, so no event can be processed between the
+    /// individual field updates — compute the set off the hot path and
+    /// apply it between `process_sensor` calls.
+    pub fn apply_parameters(&mut self, set: ParameterSet) {
+        self.reactive.gain = set.gain;
+        self.reactive.dead_zone = set.dead_zone;
+        self.reactive.min_gain_step = set.min_gain_step;
+        self.reactive.gain_band = set.gain_band;
+        self.reactive.response = set.response;
+        self.adaptive.energy_threshold = set.energy_threshold;
+        self.adaptive.tau = set.tau;
+    }
+
+    /// Move the reference of both the reactive and the adaptive context,
+    /// so `error_avg` tracks the same error the reactive layer corrects.
+    pub fn set_setpoint(&mut self, setpoint: f64) {
+        self.reactive.setpoint = setpoint;
+        self.adaptive.setpoint = setpoint;
+    }
+
+    /// Add a context processor at a further timescale.
+    ///
+    /// Registered processors see every event after the reactive, adaptive
+    /// and environmental layers, in registration order; the events they
+    /// generate re-enter the queue within the same cascade. Like the
+    /// learning layers they are skipped while frozen.
+    ///
+    /// This is an extension list, not a registry of every layer: the three
+    /// built-ins stay typed fields that run (and decay) first, so a new
+    /// timescale cannot be placed between them. Disable a built-in through
+    /// `layers` and register a replacement to change the order.
+    pub fn register(&mut self, processor: Box<dyn ContextProcessor>) {
+        self.processors.push((processor, None));
+    }
+
+    /// Number of processors added through `register`.
+    pub fn registered(&self) -> usize {
+        self.processors.len()
+    }
+
+    /// Record processed events into a ring of `capacity` entries.
+    pub fn enable_history(&mut self, capacity: usize) {
+        self.history = Some(EventHistory::new(capacity));
+    }
+
+    /// Start capturing every processed event (and each `CascadeComplete`)
+    /// for `replay`. Now becomes the time origin of the log and of the
+    /// adaptive and decay clocks; start from a fresh system for an exact
+    /// reproduction. Restarting discards the previous log.
+    #[cfg(feature = "std")]
+    pub fn enable_recording(&mut self) {
+        self.enable_recording_at(Instant::now());
+    }
+
+    /// `enable_recording` with an explicit time origin.
+    pub fn enable_recording_at(&mut self, origin: Instant) {
+        self.adaptive.last_update = Some(origin);
+        self.last_decay = Some(origin);
+        self.recording = Some((origin, Vec::new()));
+    }
+
+    /// Stop recording and return the captured log (empty if not recording).
+    pub fn take_log(&mut self) -> Vec<RecordedEvent> {
+        self.recording.take().map(|(_, log)| log).unwrap_or_default()
+    }
+
+    /// Re-run a recorded log against this system.
+    ///
+    /// Each event is delivered to the contexts in its recorded order at
+    /// its recorded offset from a new origin; the events they generate are
+    /// dropped because the log already holds them. Each `CascadeComplete`
+    /// closes a cascade (best-gain checkpoint, decay). On a fresh system
+    /// with the same configuration this reproduces the recorded run's
+    /// gain, position and counters exactly.
+    #[cfg(feature = "std")]
+    pub fn replay(&mut self, log: &[RecordedEvent]) {
+        self.replay_at(log, Instant::now());
+    }
+
+    /// `replay` from an explicit time origin.
+    pub fn replay_at(&mut self, log: &[RecordedEvent], origin: Instant) {
+        self.adaptive.last_update = Some(origin);
+        self.last_decay = Some(origin);
+        for record in log {
+            let event = record.to_event(origin);
+            if event.kind == EventKind::CascadeComplete {
+                self.finish_cascade(event.timestamp);
+            } else {
+                self.dispatch(&event, false);
+            }
+        }
+    }
+
+    /// The gain in effect when `error_avg` reached its lowest value so far.
+    ///
+    /// Checked at the end of each cascade in which the adaptive layer
+    /// averaged a sensor error; None until then, and unchanged while
+    /// frozen or with the adaptive layer disabled.
+    pub fn best_gain(&self) -> Option<f64> {
+        self.best.map(|(_, gain)| gain)
+    }
+
+    /// Return the reactive gain to `best_gain`, discarding any pending
+    /// hysteresis. Returns false if no error has been observed yet.
+    pub fn restore_best_gain(&mut self) -> bool {
+        match self.best_gain() {
+            Some(gain) => {
+                self.reactive.gain = gain;
+                self.reactive.pending_gain = 0.0;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Lock learning: events reach only the reactive layer, so the gain
+    /// and adaptive/environmental state stay fixed, and nothing decays.
+    pub fn freeze(&mut self) {
+        self.frozen = true;
+    }
+
+    /// Resume learning after `freeze`.
+    pub fn unfreeze(&mut self) {
+        self.frozen = false;
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Events lost to queue overflow (rejected or evicted) so far — the
+    /// backpressure signal under `OverflowPolicy::Reject`/`DropOldest`.
+    pub fn lost_events(&self) -> usize {
+        self.lost_events
+    }
+
+    fn enqueue(&mut self, event: Event) {
+        if self.event_queue.enqueue(event).lost_event() {
+            self.lost_events += 1;
+        }
+    }
+
+    /// Activation check with the context's refractory period applied.
+    /// Records the event time when the context is about to fire.
+    fn ready(
+        last_fired: &mut Option<Instant>,
+        context: &(impl ContextProcessor + ?Sized),
+        event: &Event,
+    ) -> bool {
+        if !context.should_activate(event) {
+            return false;
+        }
+        let window = context.activation_hysteresis();
+        if let Some(last) = *last_fired {
+            if window > 0.0
+                && event.timestamp.saturating_duration_since(last).as_secs_f64() < window
+            {
+                return false;
+            }
+        }
+        *last_fired = Some(event.timestamp);
+        true
+    }
+
+    /// Register a callback fired after each `process_sensor` whose cascade
+    /// changed the reactive position or gain. Replaces any previous one.
+    pub fn on_output(&mut self, callback: impl FnMut(OutputSample) + 'static) {
+        self.output = Some(Box::new(callback));
+    }
+
+    /// Add an event listener; listeners run in registration order.
+    ///
+    /// During propagation a listener sees every event three ways:
+    ///   - offered to the queue, with its producer (`"sensor"` for the
+    ///     injected reading, else the generating context's name);
+    ///   - taken off the queue, as `"queue"`;
+    ///   - consumed by each context that activates on it, with its name.
+    ///
+    /// Listeners only observe: propagation is identical with or without them.
+    pub fn on_event(&mut self, listener: impl FnMut(&Event, &str) + 'static) {
+        self.listeners.push(Box::new(listener));
+    }
+
+    fn notify(listeners: &mut [EventListener], event: &Event, source: &str) {
+        for listener in listeners {
+            listener(event, source);
         }
     }
 
+    /// Announce `event` as produced by `source`, then enqueue it.
+    fn offer(&mut self, event: Event, source: &str) {
+        Self::notify(&mut self.listeners, &event, source);
+        self.enqueue(event);
+    }
+
+    /// Fuse simultaneous readings into one sensor event and propagate it.
+    ///
+    /// Each reading is (value, weight); the fused error is the weighted
+    /// mean Σwᵢvᵢ / Σwᵢ, so low-confidence sensors barely perturb it.
+    /// Non-positive weights are ignored; if none remain nothing is injected
+    /// and no `CascadeComplete` is returned.
+    #[cfg(feature = "std")]
+    pub fn process_sensors(&mut self, readings: &[(f64, f64)]) -> Option<Event> {
+        self.process_sensors_at(readings, Instant::now())
+    }
+
+    /// `process_sensors` for readings taken at `at`.
+    pub fn process_sensors_at(&mut self, readings: &[(f64, f64)], at: Instant) -> Option<Event> {
+        let (sum, total) = readings
+            .iter()
+            .filter(|(_, w)| *w > 0.0)
+            .fold((0.0, 0.0), |(s, t), (v, w)| (s + v * w, t + w));
+        (total > 0.0).then(|| self.process_sensor_at(sum / total, at))
+    }
+
     /// Inject a sensor event and propagate through all contexts.
-    pub fn process_sensor(&mut self, sensor_value: f64) {
-        self.event_queue.enqueue(Event::new(
-            EventKind::SensorChange,
-            sensor_value,
-        ));
-
-        // Propagate all events
-        let mut iterations = 0;
-        while !self.event_queue.is_empty() && iterations < 100 {
-            if let Some(event) = self.event_queue.dequeue() {
-                // Each context resonates if the event matches
-                if self.reactive.should_activate(&event) {
-                    if let Some(new_event) = self.reactive.process(&event) {
-                        self.event_queue.enqueue(new_event);
-                    }
+    ///
+    /// Events drain in priority order (FIFO among equals), so a gain
+    /// adjustment preempts queued sensor traffic. The cascade stops early
+    /// on a repeating event pattern or after `MAX_CASCADE_EVENTS`.
+    ///
+    /// Returns the `CascadeComplete` event marking that the cascade
+    /// ended; its magnitude is the number of events processed. It is
+    /// recorded in the history but never propagated.
+    #[cfg(feature = "std")]
+    pub fn process_sensor(&mut self, sensor_value: f64) -> Event {
+        self.process_sensor_at(sensor_value, Instant::now())
+    }
+
+    /// `process_sensor` for a reading taken at `at`. Every event of the
+    /// cascade carries this timestamp, which also drives the refractory
+    /// periods, the adaptive averages and decay.
+    pub fn process_sensor_at(&mut self, sensor_value: f64, at: Instant) -> Event {
+        self.propagate(sensor_value, at, None).0
+    }
+
+    /// `process_sensor`, reporting how the cascade terminated.
+    ///
+    /// A cycle or the cap leaves the remaining events dropped from the
+    /// queue, so the next sample starts from a clean cascade.
+    #[cfg(feature = "std")]
+    pub fn process_sensor_outcome(&mut self, sensor_value: f64) -> PropagationOutcome {
+        self.process_sensor_outcome_at(sensor_value, Instant::now())
+    }
+
+    /// `process_sensor_outcome` for a reading taken at `at`.
+    pub fn process_sensor_outcome_at(
+        &mut self,
+        sensor_value: f64,
+        at: Instant,
+    ) -> PropagationOutcome {
+        self.propagate(sensor_value, at, None).1
+    }
+
+    /// Process a batch of sensor values, recording the state after each.
+    ///
+    /// One `StepRecord` per sample: the reactive/adaptive metrics once the
+    /// cascade settled, plus every event processed during it.
+    #[cfg(feature = "std")]
+    pub fn process_batch_recorded(&mut self, samples: &[f64]) -> Vec<StepRecord> {
+        samples
+            .iter()
+            .map(|&value| {
+                let mut events = Vec::new();
+                self.propagate(value, Instant::now(), Some(&mut events));
+                StepRecord {
+                    sensor_value: value,
+                    position: self.reactive.position,
+                    gain: self.reactive.gain,
+                    error_avg: self.adaptive.error_avg,
+                    movement_avg: self.adaptive.movement_avg,
+                    energy: self.adaptive.accumulated_energy,
+                    events,
                 }
-                if self.adaptive.should_activate(&event) {
-                    if let Some(new_event) = self.adaptive.process(&event) {
-                        self.event_queue.enqueue(new_event);
-                    }
+            })
+            .collect()
+    }
+
+    /// Deliver one event to every context that resonates with it and is
+    /// not inside its refractory period. Generated events are offered to
+    /// the queue when `enqueue` is set and dropped otherwise.
+    fn dispatch(&mut self, event: &Event, enqueue: bool) {
+        let learning = !self.frozen;
+        if self.layers.reactive && Self::ready(&mut self.last_fired[0], &self.reactive, event) {
+            Self::notify(&mut self.listeners, event, self.reactive.name());
+            if let Some(new_event) = self.reactive.process(event) {
+                if enqueue {
+                    self.offer(new_event, "reactive");
+                }
+            }
+        }
+        if learning
+            && self.layers.adaptive
+            && Self::ready(&mut self.last_fired[1], &self.adaptive, event)
+        {
+            Self::notify(&mut self.listeners, event, self.adaptive.name());
+            if let Some(new_event) = self.adaptive.process(event) {
+                if enqueue {
+                    self.offer(new_event, "adaptive");
+                }
+            }
+        }
+        if learning
+            && self.layers.environmental
+            && Self::ready(&mut self.last_fired[2], &self.environmental, event)
+        {
+            Self::notify(&mut self.listeners, event, self.environmental.name());
+            if let Some(new_event) = self.environmental.process(event) {
+                if enqueue {
+                    self.offer(new_event, "environmental");
                 }
-                if self.environmental.should_activate(&event) {
-                    if let Some(new_event) = self.environmental.process(&event) {
-                        self.event_queue.enqueue(new_event);
+            }
+        }
+        if learning {
+            let mut generated = Vec::new();
+            for (processor, last_fired) in &mut self.processors {
+                if Self::ready(last_fired, processor.as_ref(), event) {
+                    Self::notify(&mut self.listeners, event, processor.name());
+                    if let Some(new_event) = processor.process(event) {
+                        if enqueue {
+                            Self::notify(&mut self.listeners, &new_event, processor.name());
+                            generated.push(new_event);
+                        }
                     }
                 }
             }
-            iterations += 1;
+            for new_event in generated {
+                self.enqueue(new_event);
+            }
+        }
+    }
+
+    /// End-of-cascade bookkeeping at time `at`: checkpoint the best gain
+    /// and apply temporal decay since the previous cascade.
+    fn finish_cascade(&mut self, at: Instant) {
+        // Checkpoint the gain behind the best error seen so far, but only
+        // when a sensor error reached `error_avg` in this cascade: decay
+        // alone would otherwise pass for an improvement
+        let samples = self.adaptive.error_samples;
+        if self.layers.adaptive && !self.frozen && samples != self.best_checked {
+            self.best_checked = samples;
+            let error_avg = self.adaptive.error_avg;
+            if self.best.is_none_or(|(best, _)| error_avg < best) {
+                self.best = Some((error_avg, self.reactive.gain));
+            }
         }
 
-        // Apply temporal decay
-        let dt = self.last_decay.elapsed().as_secs_f64();
+        // Apply temporal decay (time spent frozen is skipped, not deferred)
+        let Some(last_decay) = self.last_decay else {
+            self.last_decay = Some(at);
+            return;
+        };
+        let dt = at.saturating_duration_since(last_decay).as_secs_f64();
         if dt > 0.001 {
-            self.adaptive.decay(dt);
-            self.environmental.decay(dt);
-            self.last_decay = Instant::now();
+            if self.layers.adaptive && !self.frozen {
+                self.adaptive.decay(dt);
+            }
+            if self.layers.environmental && !self.frozen {
+                self.environmental.decay(dt);
+            }
+            if !self.frozen {
+                for (processor, _) in &mut self.processors {
+                    processor.decay(dt);
+                }
+            }
+            self.last_decay = Some(at);
+        }
+    }
+
+    /// `process_sensor_at`, optionally collecting every processed event.
+    fn propagate(
+        &mut self,
+        sensor_value: f64,
+        at: Instant,
+        mut sink: Option<&mut Vec<Event>>,
+    ) -> (Event, PropagationOutcome) {
+        let sensor = Event::at_time(EventKind::SensorChange, sensor_value, EventPayload::None, at);
+        self.offer(sensor, "sensor");
+        let before = (self.reactive.position, self.reactive.gain);
+
+        // Propagate until quiescent, a cycle, or the backstop
+        let mut cycles = CycleDetector::new();
+        let mut processed = 0usize;
+        let mut terminated_by = Reason::Quiescent;
+        while let Some(event) = self.event_queue.dequeue_highest() {
+            if processed == MAX_CASCADE_EVENTS {
+                terminated_by = Reason::IterationCap;
+                break;
+            }
+            processed += 1;
+            Self::notify(&mut self.listeners, &event, "queue");
+            if let Some(history) = self.history.as_mut() {
+                history.push(event.clone());
+            }
+            if let Some(sink) = sink.as_mut() {
+                sink.push(event.clone());
+            }
+            if let Some((origin, log)) = self.recording.as_mut() {
+                log.push(RecordedEvent::capture(&event, *origin));
+            }
+            self.dispatch(&event, true);
+            if cycles.observe(&event) {
+                terminated_by = Reason::CycleDetected;
+                break;
+            }
+        }
+        if terminated_by != Reason::Quiescent {
+            sic_warn!(
+                "cascade stopped by {:?} after {} events, dropping {}",
+                terminated_by,
+                processed,
+                self.event_queue.len()
+            );
+            while self.event_queue.dequeue_highest().is_some() {}
+        }
+
+        let complete =
+            Event::at_time(EventKind::CascadeComplete, processed as f64, EventPayload::None, at);
+        self.finish_cascade(complete.timestamp);
+
+        if let Some(output) = self.output.as_mut() {
+            let (position, gain) = (self.reactive.position, self.reactive.gain);
+            if (position, gain) != before {
+                output(OutputSample {
+                    position,
+                    gain,
+                    t: at,
+                });
+            }
+        }
+
+        if let Some((origin, log)) = self.recording.as_mut() {
+            log.push(RecordedEvent::capture(&complete, *origin));
+        }
+        if let Some(history) = self.history.as_mut() {
+            history.push(complete.clone());
+        }
+        let outcome = PropagationOutcome {
+            events_processed: processed,
+            terminated_by,
+        };
+        (complete, outcome)
+    }
+}
+
+/// Chainable construction of a tuned `NestedLearningSystem`.
+///
+/// Starts from the same defaults as `NestedLearningSystem::with_config`,
+/// so an untouched builder reproduces `new()` exactly.
+pub struct NestedLearningBuilder {
+    config: SicConfig,
+    reactive: ReactiveContext,
+    adaptive: AdaptiveContext,
+    environmental: EnvironmentalContext,
+    layers: Layers,
+    processors: Vec<Box<dyn ContextProcessor>>,
+    queue_capacity: usize,
+    overflow_policy: OverflowPolicy,
+}
+
+impl Default for NestedLearningBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NestedLearningBuilder {
+    pub fn new() -> Self {
+        Self::from_config(&SicConfig::default())
+    }
+
+    /// Start from custom normalization constants.
+    pub fn from_config(config: &SicConfig) -> Self {
+        Self {
+            config: config.clone(),
+            reactive: ReactiveContext::with_config(config),
+            adaptive: AdaptiveContext::with_config(config),
+            environmental: EnvironmentalContext::new(),
+            layers: Layers::ALL,
+            processors: Vec::new(),
+            queue_capacity: 32,
+            overflow_policy: OverflowPolicy::Reject,
+        }
+    }
+
+    pub fn reactive_gain(mut self, gain: f64) -> Self {
+        self.reactive.gain = gain;
+        self
+    }
+
+    pub fn dead_zone(mut self, dead_zone: f64) -> Self {
+        self.reactive.dead_zone = dead_zone;
+        self
+    }
+
+    pub fn response(mut self, response: ResponseCurve) -> Self {
+        self.reactive.response = response;
+        self
+    }
+
+    pub fn safe_gain(mut self, safe_gain: f64) -> Self {
+        self.reactive.safe_gain = safe_gain;
+        self
+    }
+
+    /// Reference of the reactive and adaptive contexts.
+    pub fn setpoint(mut self, setpoint: f64) -> Self {
+        self.reactive.setpoint = setpoint;
+        self.adaptive.setpoint = setpoint;
+        self
+    }
+
+    pub fn adaptive_tau(mut self, tau: f64) -> Self {
+        self.adaptive.tau = tau;
+        self
+    }
+
+    pub fn energy_threshold(mut self, threshold: f64) -> Self {
+        self.adaptive.energy_threshold = threshold;
+        self
+    }
+
+    /// Replace the adaptation rules (default: `AdaptationRule::defaults`).
+    pub fn rules(mut self, rules: Vec<AdaptationRule>) -> Self {
+        *self.adaptive.rules_mut() = rules;
+        self
+    }
+
+    pub fn environmental_sample_threshold(mut self, samples: usize) -> Self {
+        self.environmental.sample_threshold = samples;
+        self
+    }
+
+    /// Oscillation ratios that widen and narrow the allowed ranges.
+    pub fn oscillation_thresholds(mut self, widen: f64, narrow: f64) -> Self {
+        self.environmental.widen_osc_threshold = widen;
+        self.environmental.narrow_osc_threshold = narrow;
+        self
+    }
+
+    /// Runaway ratio and consecutive count that trigger safe mode.
+    pub fn safe_mode_trigger(mut self, osc_threshold: f64, count: usize) -> Self {
+        self.environmental.safe_osc_threshold = osc_threshold;
+        self.environmental.safe_trigger_count = count;
+        self
+    }
+
+    pub fn layers(mut self, layers: Layers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Add a processor, as `NestedLearningSystem::register`.
+    pub fn register(mut self, processor: Box<dyn ContextProcessor>) -> Self {
+        self.processors.push(processor);
+        self
+    }
+
+    pub fn queue(mut self, capacity: usize, policy: OverflowPolicy) -> Self {
+        self.queue_capacity = capacity;
+        self.overflow_policy = policy;
+        self
+    }
+
+    pub fn build(self) -> NestedLearningSystem {
+        let mut system = NestedLearningSystem::with_layers(&self.config, self.layers);
+        system.reactive = self.reactive;
+        system.adaptive = self.adaptive;
+        system.environmental = self.environmental;
+        system.event_queue = EventQueue::with_policy(self.queue_capacity, self.overflow_policy);
+        for processor in self.processors {
+            system.register(processor);
+        }
+        system
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::rc::Rc;
+    use core::cell::{Cell, RefCell};
+    use core::time::Duration;
+    #[cfg(feature = "std")]
+    use std::rc::Rc;
+
+    /// Sample times 10 ms apart from an arbitrary origin.
+    struct Clock(Instant);
+
+    impl Clock {
+        fn new() -> Self {
+            #[cfg(feature = "std")]
+            let origin = Instant::now();
+            #[cfg(not(feature = "std"))]
+            let origin = Instant::from_millis(0);
+            Self(origin)
         }
+
+        fn tick(&mut self) -> Instant {
+            self.0 += Duration::from_millis(10);
+            self.0
+        }
+    }
+
+    #[test]
+    fn adaptive_error_is_measured_from_the_setpoint() {
+        let mut clock = Clock::new();
+        let mut system = NestedLearningSystem::builder().setpoint(100.0).build();
+        for _ in 0..5 {
+            system.process_sensor_at(100.0, clock.tick());
+        }
+        assert_eq!(system.adaptive.error_avg, 0.0);
+
+        system.set_setpoint(0.0);
+        system.process_sensor_at(100.0, clock.tick());
+        assert!(system.adaptive.error_avg > 0.0);
+    }
+
+    #[test]
+    fn non_default_config_changes_the_response() {
+        let mut clock = Clock::new();
+        let config = SicConfig {
+            max_step: 2.0 * SicConfig::default().max_step,
+            ..SicConfig::default()
+        };
+        let mut default = NestedLearningSystem::new();
+        let mut tuned = NestedLearningSystem::with_config(&config);
+        default.process_sensor_at(200.0, clock.tick());
+        tuned.process_sensor_at(200.0, clock.tick());
+        let moved = |s: &NestedLearningSystem| s.reactive.position - 90.0;
+        assert!(moved(&default) > 0.0);
+        assert!((moved(&tuned) - 2.0 * moved(&default)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn each_system_keeps_its_own_time() {
+        let (mut early, mut late) = (Clock::new(), Clock::new());
+        for _ in 0..100 {
+            late.tick();
+        }
+        let mut a = NestedLearningSystem::new();
+        let mut b = NestedLearningSystem::new();
+        a.enable_history(16);
+        let (ta, tb) = (early.tick(), late.tick());
+        assert_eq!(a.process_sensor_at(300.0, ta).timestamp, ta);
+        assert_eq!(b.process_sensor_at(300.0, tb).timestamp, tb);
+        assert!(a.history.as_ref().unwrap().iter().all(|e| e.timestamp == ta));
+        assert_eq!(a.adaptive.last_update(), Some(ta));
+        assert_eq!(b.adaptive.last_update(), Some(tb));
+    }
+
+    /// A system whose gain only changes when the test sets it.
+    fn fixed_gain_system() -> NestedLearningSystem {
+        NestedLearningSystem::builder().energy_threshold(f64::INFINITY).build()
+    }
+
+    #[test]
+    fn best_gain_captures_the_mid_run_optimum() {
+        let mut clock = Clock::new();
+        let mut system = fixed_gain_system();
+        assert_eq!(system.best_gain(), None);
+        for (gain, sensor) in [(0.5, 400.0), (1.2, 5.0), (1.8, 400.0)] {
+            system.reactive.gain = gain;
+            for _ in 0..200 {
+                system.process_sensor_at(sensor, clock.tick());
+            }
+        }
+        assert_eq!(system.best_gain(), Some(1.2));
+        assert!(system.restore_best_gain());
+        assert_eq!(system.reactive.gain, 1.2);
+    }
+
+    #[test]
+    fn first_sample_seeds_the_error_average() {
+        let mut clock = Clock::new();
+        let mut system = fixed_gain_system();
+        system.process_sensor_at(400.0, clock.tick());
+        let seeded = system.adaptive.error_avg;
+        assert!(seeded > 0.5, "warm-up must not start from 0, got {seeded}");
+        assert_eq!(system.best_gain(), Some(system.reactive.gain));
+    }
+
+    #[test]
+    fn best_gain_skips_frozen_and_disabled_adaptive() {
+        let mut clock = Clock::new();
+        let mut system = fixed_gain_system();
+        system.freeze();
+        system.process_sensor_at(5.0, clock.tick());
+        assert_eq!(system.best_gain(), None);
+
+        let mut system = NestedLearningSystem::builder()
+            .layers(Layers { adaptive: false, ..Layers::ALL })
+            .build();
+        system.process_sensor_at(5.0, clock.tick());
+        assert_eq!(system.best_gain(), None);
+    }
+
+    fn adjust(change: f64, at: Instant) -> Event {
+        Event::at_time(EventKind::ParameterAdjust, change, EventPayload::None, at)
+    }
+
+    #[test]
+    fn alternating_adjustments_do_not_chatter() {
+        let mut clock = Clock::new();
+        let mut reactive = ReactiveContext::new();
+        for k in 0..50 {
+            let change = if k % 2 == 0 { 0.1 } else { -0.1 };
+            reactive.process(&adjust(change, clock.tick()));
+            assert_eq!(reactive.gain, 0.5);
+        }
+
+        // a sustained push leaves the band and is applied at once
+        reactive.process(&adjust(0.1, clock.tick()));
+        reactive.process(&adjust(0.1, clock.tick()));
+        assert!((reactive.gain - 0.7).abs() < 1e-12);
+        assert_eq!(reactive.pending_gain(), 0.0);
+    }
+
+    #[test]
+    fn low_confidence_sensor_barely_perturbs_the_fusion() {
+        let mut clock = Clock::new();
+        let mut system = NestedLearningSystem::new();
+        system.enable_history(64);
+        assert!(system.process_sensors_at(&[(100.0, 1.0), (400.0, 0.01)], clock.tick()).is_some());
+        let fused = system
+            .history
+            .as_ref()
+            .unwrap()
+            .iter()
+            .find(|e| e.kind == EventKind::SensorChange)
+            .unwrap()
+            .magnitude;
+        assert!((fused - 104.0 / 1.01).abs() < 1e-9);
+        assert!((fused - 100.0).abs() < 0.01 * (400.0 - 100.0));
+
+        assert!(system.process_sensors_at(&[(100.0, 0.0), (400.0, -1.0)], clock.tick()).is_none());
+    }
+
+    #[test]
+    fn closure_context_doubles_movements() {
+        let mut clock = Clock::new();
+        let mut system = NestedLearningSystem::new();
+        system.enable_history(64);
+        system.register(Box::new(FnContext::new(
+            |e| e.kind == EventKind::Movement,
+            |e| {
+                let kind = EventKind::Custom("double".to_string());
+                Some(Event::at_time(kind, 2.0 * e.magnitude, e.payload, e.timestamp))
+            },
+        )));
+        system.process_sensor_at(300.0, clock.tick());
+
+        let history: Vec<&Event> = system.history.as_ref().unwrap().iter().collect();
+        let movement = history.iter().find(|e| e.kind == EventKind::Movement).unwrap();
+        let double = history.iter().find(|e| matches!(e.kind, EventKind::Custom(_))).unwrap();
+        assert!(movement.magnitude > 0.0);
+        assert_eq!(double.magnitude, 2.0 * movement.magnitude);
+    }
+
+    fn sensor(value: f64, at: Instant) -> Event {
+        Event::at_time(EventKind::SensorChange, value, EventPayload::None, at)
+    }
+
+    fn moved(degrees: f64, at: Instant) -> Event {
+        Event::at_time(EventKind::Movement, degrees, EventPayload::None, at)
+    }
+
+    #[test]
+    fn response_curves_diverge_mid_range_and_meet_at_full_scale() {
+        let mut clock = Clock::new();
+        let step = |curve, error: f64, at| {
+            let mut reactive = ReactiveContext::new();
+            reactive.response = curve;
+            let value = error * reactive.sensor_full_scale;
+            let moved = reactive.process(&sensor(value, at)).unwrap().magnitude;
+            moved / (reactive.gain * reactive.max_step)
+        };
+        let curves = [ResponseCurve::Quadratic, ResponseCurve::Linear, ResponseCurve::Tanh];
+
+        let mid: Vec<f64> = curves.iter().map(|&c| step(c, 0.5, clock.tick())).collect();
+        assert!((mid[0] - 0.25).abs() < 1e-12);
+        assert!((mid[1] - 0.5).abs() < 1e-12);
+        assert!((mid[2] - 1f64.tanh() / 2f64.tanh()).abs() < 1e-12);
+        assert!(mid[0] < mid[1] && mid[1] < mid[2]);
+
+        for curve in curves {
+            assert!((step(curve, 1.0, clock.tick()) - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn applying_a_parameter_set_changes_every_field() {
+        let mut system = NestedLearningSystem::new();
+        let before = system.parameters();
+        let set = ParameterSet {
+            gain: 1.5,
+            dead_zone: 12.0,
+            min_gain_step: 0.01,
+            gain_band: 0.05,
+            response: ResponseCurve::Tanh,
+            energy_threshold: 250.0,
+            tau: 3.0,
+        };
+        assert!(set.gain != before.gain && set.response != before.response);
+        system.apply_parameters(set.clone());
+        assert_eq!(system.parameters(), set);
+        assert_eq!(system.reactive.dead_zone, 12.0);
+        assert_eq!(system.adaptive.tau, 3.0);
+
+        system.apply_parameters(before.clone());
+        assert_eq!(system.parameters(), before);
+    }
+
+    #[test]
+    fn pushing_against_a_boundary_emits_no_movement() {
+        let mut clock = Clock::new();
+        let mut reactive = ReactiveContext::new();
+        reactive.position = 5.0;
+        // the part of the step up to the boundary is still reported
+        let first = reactive.process(&sensor(-1000.0, clock.tick())).unwrap();
+        assert_eq!((first.magnitude, reactive.position), (5.0, 0.0));
+        for _ in 0..5 {
+            assert!(reactive.process(&sensor(-1000.0, clock.tick())).is_none());
+            assert_eq!(reactive.position, 0.0);
+        }
+        // moving away from the boundary still reports the movement
+        let back = reactive.process(&sensor(200.0, clock.tick())).unwrap();
+        assert_eq!(back.magnitude, reactive.position);
+    }
+
+    #[test]
+    fn last_rule_fired_reports_the_matching_rule() {
+        let mut clock = Clock::new();
+        let mut adaptive = AdaptiveContext::new();
+        adaptive.energy_threshold = 0.0;
+        assert_eq!(adaptive.last_rule_fired_at(clock.tick()), None);
+
+        // full-scale movement and error: nervous system, RULE 1
+        let (movement, error) = (adaptive.movement_full_scale, adaptive.sensor_full_scale);
+        let mut last = None;
+        for _ in 0..100 {
+            let at = clock.tick();
+            adaptive.process(&moved(movement, at));
+            last = adaptive.process(&sensor(error, at));
+        }
+        assert!(adaptive.movement_avg > 0.6 && adaptive.error_avg > 0.1);
+        assert_eq!(last.unwrap().magnitude, -0.15);
+        let now = clock.tick();
+        assert_eq!(adaptive.last_rule_fired_at(now), Some((0, 0.01)));
+
+        // still movement with the same error: slow system, RULE 2
+        for _ in 0..200 {
+            let at = clock.tick();
+            adaptive.process(&moved(0.0, at));
+            adaptive.process(&sensor(error, at));
+        }
+        assert!(adaptive.movement_avg < 0.2);
+        assert_eq!(adaptive.last_rule_fired_at(adaptive.last_update().unwrap()), Some((1, 0.0)));
+    }
+
+    #[test]
+    fn sustained_oscillation_collapses_the_gain_to_safe_mode() {
+        let mut clock = Clock::new();
+        // a pathological learner that flips the gain on every sample
+        let mut flip = 1i8;
+        let chatter = FnContext::new(
+            |e| e.kind == EventKind::SensorChange,
+            move |e| {
+                flip = -flip;
+                let change = 0.3 * flip as f64;
+                let payload = EventPayload::Direction(flip);
+                Some(Event::at_time(EventKind::ParameterAdjust, change, payload, e.timestamp))
+            },
+        );
+        let mut system = NestedLearningSystem::builder()
+            .energy_threshold(f64::INFINITY)
+            .environmental_sample_threshold(4)
+            .safe_mode_trigger(0.7, 3)
+            .safe_gain(0.1)
+            .register(Box::new(chatter))
+            .build();
+
+        let mut gains = Vec::new();
+        for _ in 0..12 {
+            system.process_sensor_at(0.0, clock.tick());
+            gains.push(system.reactive.gain);
+        }
+        // the gain chatters for three runaway windows, then snaps to safe mode
+        assert!(gains[..11].iter().all(|&g| g == 0.2 || g == 0.5), "{gains:?}");
+        assert_eq!(gains[11], 0.1);
+    }
+
+    #[test]
+    fn a_mutated_rule_threshold_moves_the_firing_boundary() {
+        let mut clock = Clock::new();
+        let run = |adaptive: &mut AdaptiveContext, at| {
+            adaptive.energy_threshold = 0.0;
+            // still, with a normalized error of 0.15
+            adaptive.process(&sensor(0.15 * adaptive.sensor_full_scale, at))
+        };
+
+        let mut stock = AdaptiveContext::new();
+        assert!(run(&mut stock, clock.tick()).is_none());
+        assert_eq!(stock.last_rule_fired_at(clock.tick()), None);
+
+        let mut tuned = AdaptiveContext::new();
+        *tuned.rules_mut()[1].error.bound_mut() = 0.1;
+        assert_eq!(tuned.rules()[1].error, Threshold::Above(0.1));
+        let adjust = run(&mut tuned, clock.tick()).unwrap();
+        assert_eq!(adjust.magnitude, 0.15);
+        assert_eq!(tuned.last_rule_fired_at(adjust.timestamp), Some((1, 0.0)));
+    }
+
+    #[test]
+    fn setpoint_step_kicks_only_the_derivative_on_error() {
+        let step_response = |mode| {
+            let mut clock = Clock::new();
+            let mut reactive = ReactiveContext::new();
+            reactive.dead_zone = 0.0;
+            reactive.derivative_gain = 0.01;
+            reactive.derivative_mode = mode;
+            reactive.setpoint = 100.0;
+            assert!(reactive.process(&sensor(100.0, clock.tick())).is_none());
+            // same measurement, new reference
+            reactive.setpoint = 0.0;
+            reactive.process(&sensor(100.0, clock.tick())).unwrap().magnitude
+        };
+        let r = ReactiveContext::new();
+        let proportional = r.gain * (100.0 / r.sensor_full_scale) * r.max_step;
+
+        let on_measurement = step_response(DerivativeMode::OnMeasurement);
+        assert!((on_measurement - proportional).abs() < 1e-12);
+        // Δerror = 100 over 10 ms
+        let kick = step_response(DerivativeMode::OnError) - proportional;
+        assert!((kick - 0.01 * 100.0 / 0.01 / r.sensor_full_scale * r.max_step).abs() < 1e-9);
+    }
+
+    #[test]
+    fn small_persistent_error_is_eventually_corrected() {
+        let mut clock = Clock::new();
+        let mut reactive = ReactiveContext::new();
+        reactive.integral_threshold = 100.0;
+        reactive.integral_step = 2.0;
+        // 10 is inside the 30-wide dead zone
+        for _ in 0..9 {
+            assert!(reactive.process(&sensor(10.0, clock.tick())).is_none());
+        }
+        assert_eq!(reactive.integral(), 90.0);
+        let nudge = reactive.process(&sensor(10.0, clock.tick())).unwrap();
+        assert_eq!(nudge.magnitude, 2.0);
+        assert_eq!(reactive.position, 92.0);
+        assert_eq!(reactive.integral(), 0.0);
+
+        // disabled by default
+        let mut plain = ReactiveContext::new();
+        for _ in 0..50 {
+            assert!(plain.process(&sensor(10.0, clock.tick())).is_none());
+        }
+        assert_eq!(plain.position, 90.0);
+    }
+
+    /// Kinds of every event processed over `samples`.
+    fn observed_kinds(system: &mut NestedLearningSystem, samples: &[f64]) -> Vec<EventKind> {
+        system.enable_history(16 * samples.len());
+        let mut clock = Clock::new();
+        for &value in samples {
+            system.process_sensor_at(value, clock.tick());
+        }
+        system.history.as_ref().unwrap().iter().map(|e| e.kind.clone()).collect()
+    }
+
+    #[test]
+    fn two_tier_system_emits_no_environment_changes() {
+        let swinging: Vec<f64> =
+            (0..300).map(|k| if k % 2 == 0 { 400.0 } else { -400.0 }).collect();
+        let build = |layers| {
+            NestedLearningSystem::builder()
+                .energy_threshold(50.0)
+                .environmental_sample_threshold(10)
+                .layers(layers)
+                .build()
+        };
+
+        let full = observed_kinds(&mut build(Layers::ALL), &swinging);
+        assert!(full.contains(&EventKind::EnvironmentChange));
+
+        let two_tier = observed_kinds(&mut build(Layers::TWO_TIER), &swinging);
+        assert!(two_tier.contains(&EventKind::ParameterAdjust));
+        assert!(!two_tier.contains(&EventKind::EnvironmentChange));
+    }
+
+    #[test]
+    fn each_sample_completes_exactly_one_cascade() {
+        let mut clock = Clock::new();
+        let mut system = NestedLearningSystem::builder().energy_threshold(50.0).build();
+        system.enable_history(4096);
+        let samples = [0.0, 400.0, -400.0, 10.0, 250.0, 250.0, -90.0];
+        for (k, &value) in samples.iter().enumerate() {
+            let done = system.process_sensor_at(value, clock.tick());
+            assert_eq!(done.kind, EventKind::CascadeComplete);
+            let completed = system
+                .history
+                .as_ref()
+                .unwrap()
+                .iter()
+                .filter(|e| e.kind == EventKind::CascadeComplete)
+                .count();
+            assert_eq!(completed, k + 1);
+        }
+    }
+
+    #[test]
+    fn output_samples_track_the_reactive_state() {
+        let mut clock = Clock::new();
+        let mut system = NestedLearningSystem::builder().energy_threshold(50.0).build();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&seen);
+        system.on_output(move |sample| sink.borrow_mut().push(sample));
+
+        let mut expected = Vec::new();
+        for value in [0.0, 400.0, 400.0, 5.0, -300.0, -300.0, 0.0, 250.0] {
+            let before = (system.reactive.position, system.reactive.gain);
+            let at = clock.tick();
+            system.process_sensor_at(value, at);
+            let (position, gain) = (system.reactive.position, system.reactive.gain);
+            if (position, gain) != before {
+                expected.push(OutputSample { position, gain, t: at });
+            }
+        }
+        assert!(expected.len() > 2);
+        assert_eq!(*seen.borrow(), expected);
+    }
+
+    #[test]
+    fn frozen_gain_never_changes() {
+        let mut clock = Clock::new();
+        let mut system = NestedLearningSystem::builder().energy_threshold(1.0).build();
+        system.freeze();
+        let gain = system.reactive.gain;
+        let start = system.reactive.position;
+        let inputs = [400.0, -400.0, 1000.0, 0.0, -1000.0, 250.0, 3.0, -750.0];
+        for (k, &value) in inputs.iter().cycle().take(200).enumerate() {
+            system.process_sensor_at(value * (1.0 + k as f64 / 100.0), clock.tick());
+            assert_eq!(system.reactive.gain, gain);
+        }
+        assert_ne!(system.reactive.position, start, "the reactive layer still controls");
+
+        // the same input adapts the gain once learning resumes
+        system.unfreeze();
+        for &value in inputs.iter().cycle().take(200) {
+            system.process_sensor_at(value, clock.tick());
+        }
+        assert_ne!(system.reactive.gain, gain);
+    }
+
+    /// Counts its process calls; refractory for 100 ms after each.
+    struct Refractory(Rc<Cell<usize>>);
+
+    impl ContextProcessor for Refractory {
+        fn should_activate(&self, event: &Event) -> bool {
+            event.kind == EventKind::SensorChange
+        }
+
+        fn process(&mut self, _event: &Event) -> Option<Event> {
+            self.0.set(self.0.get() + 1);
+            None
+        }
+
+        fn decay(&mut self, _delta_t_secs: f64) {}
+
+        fn activation_hysteresis(&self) -> f64 {
+            0.1
+        }
+    }
+
+    #[test]
+    fn rapid_repeat_inside_the_refractory_window_is_ignored() {
+        let mut clock = Clock::new();
+        let calls = Rc::new(Cell::new(0));
+        let mut system = NestedLearningSystem::new();
+        system.register(Box::new(Refractory(Rc::clone(&calls))));
+
+        system.process_sensor_at(300.0, clock.tick());
+        system.process_sensor_at(300.0, clock.tick());
+        assert_eq!(calls.get(), 1, "the repeat 10 ms later falls in the window");
+
+        clock.0 += Duration::from_millis(200);
+        system.process_sensor_at(300.0, clock.tick());
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn batch_records_one_step_per_sample_ending_at_the_live_state() {
+        let mut system = NestedLearningSystem::builder().energy_threshold(5.0).build();
+        let samples = [400.0, -200.0, 50.0, 0.0, 700.0, -650.0, 10.0];
+        let records = system.process_batch_recorded(&samples);
+
+        assert_eq!(records.len(), samples.len());
+        for (record, &value) in records.iter().zip(&samples) {
+            assert_eq!(record.sensor_value, value);
+            assert_eq!(record.events[0].kind, EventKind::SensorChange);
+        }
+        let last = records.last().unwrap();
+        assert_eq!(last.position, system.reactive.position);
+        assert_eq!(last.gain, system.reactive.gain);
+        assert_eq!(last.error_avg, system.adaptive.error_avg);
+        assert_eq!(last.movement_avg, system.adaptive.movement_avg);
+        assert_eq!(last.energy, system.adaptive.accumulated_energy);
+    }
+
+    #[test]
+    fn listener_sees_the_full_cascade() {
+        let build = || {
+            NestedLearningSystem::builder()
+                .energy_threshold(50.0)
+                .environmental_sample_threshold(10)
+                .build()
+        };
+        let mut watched = build();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&log);
+        watched.on_event(move |event, source| {
+            sink.borrow_mut().push((event.kind.clone(), source.to_string()))
+        });
+        let order = Rc::new(RefCell::new(Vec::new()));
+        for tag in ["first", "second"] {
+            let sink = Rc::clone(&order);
+            watched.on_event(move |_, _| sink.borrow_mut().push(tag));
+        }
+        let mut plain = build();
+
+        let mut clock = Clock::new();
+        let mut cascade = None;
+        for k in 0..300 {
+            let value = if k % 2 == 0 { 400.0 } else { -400.0 };
+            let at = clock.tick();
+            log.borrow_mut().clear();
+            watched.process_sensor_at(value, at);
+            plain.process_sensor_at(value, at);
+            let seen = log.borrow();
+            let assessed = seen.iter().any(|(kind, _)| *kind == EventKind::EnvironmentChange);
+            if cascade.is_none() && assessed {
+                cascade = Some(seen.clone());
+            }
+        }
+
+        let cascade = cascade.expect("the swing reaches the environmental layer");
+        let position = |kind: EventKind, source: &str| {
+            cascade.iter().position(|(k, s)| *k == kind && s == source).unwrap()
+        };
+        let sensor = position(EventKind::SensorChange, "sensor");
+        let movement = position(EventKind::Movement, "reactive");
+        let adjust = position(EventKind::ParameterAdjust, "adaptive");
+        let environment = position(EventKind::EnvironmentChange, "environmental");
+        assert!(sensor < movement && movement < adjust && adjust < environment, "{cascade:?}");
+
+        assert!(order.borrow().chunks(2).all(|pair| pair == ["first", "second"]));
+        assert_eq!(watched.reactive.gain, plain.reactive.gain);
+        assert_eq!(watched.reactive.position, plain.reactive.position);
+    }
+
+    #[test]
+    fn replay_reproduces_the_recorded_run() {
+        let build = || {
+            NestedLearningSystem::builder()
+                .energy_threshold(50.0)
+                .environmental_sample_threshold(10)
+                .build()
+        };
+        let mut clock = Clock::new();
+        let mut recorded = build();
+        recorded.enable_recording_at(clock.0);
+        for k in 0..120 {
+            let value = if k % 3 == 0 { -350.0 } else { 400.0 - k as f64 };
+            recorded.process_sensor_at(value, clock.tick());
+        }
+        let log = recorded.take_log();
+        assert!(log.iter().any(|r| r.kind == EventKind::EnvironmentChange));
+
+        // a later origin: only the recorded offsets matter
+        let mut replayed = build();
+        replayed.replay_at(&log, clock.0 + Duration::from_secs(5));
+        assert_eq!(replayed.reactive.gain, recorded.reactive.gain);
+        assert_eq!(replayed.reactive.position, recorded.reactive.position);
+        assert_eq!(replayed.adaptive.error_avg, recorded.adaptive.error_avg);
+        assert_eq!(replayed.environmental.adjustments, recorded.environmental.adjustments);
+        assert_eq!(replayed.environmental.oscillations, recorded.environmental.oscillations);
+        assert_eq!(replayed.best_gain(), recorded.best_gain());
     }
 }
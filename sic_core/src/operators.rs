@@ -4,55 +4,944 @@
 //! ×  Scalar Modulation
 //! T  Context Transformation
 
-use crate::coherence::coherence;
-use crate::context::Context;
-use crate::entity::{Entity, OwnedEntity};
+use crate::coherence::{
+    cluster_groups, coherence, coherence_with, perspective_coherence, IncrementalClusterer,
+};
+use crate::config::SicConfig;
+use crate::context::{Context, KindInheritance, MergeStrategy, Perspective, Scale};
+use crate::entity::{AsEntityRef, Entity, OwnedEntity, StepKind, TransformStep};
+use std::fmt;
 
 /// Contextual Composition ⊕ (Section 6.1.2).
 ///
 /// E₁{C₁,S₁,P₁} ⊕ E₂{C₂,S₂,P₂} = E₃{C₁∪C₂, S₁∩S₂, P₁ ⊕_P P₂}
 ///
+/// Shared parameters of C₁∪C₂ are averaged weighted by intensity, so the
+/// more intense entity dominates the composed context. The result is only
+/// as faithful as its least faithful operand.
+///
 /// This CONSUMES both entities and produces a new owned entity.
 /// The irreversibility models the commutative monoid structure:
 /// you can compose freely, but cannot always decompose.
-pub fn compose(e1: &Entity, e2: &Entity) -> OwnedEntity {
-    let coh = coherence(e1.context, e2.context);
+///
+/// Accepts borrowed and owned entities alike (see `AsEntityRef`).
+pub fn compose(e1: &impl AsEntityRef, e2: &impl AsEntityRef) -> OwnedEntity {
+    compose_config(e1, e2, &SicConfig::default())
+}
+
+/// Contextual Composition ⊕ using σ and the fusion threshold from `config`.
+pub fn compose_config(
+    e1: &impl AsEntityRef,
+    e2: &impl AsEntityRef,
+    config: &SicConfig,
+) -> OwnedEntity {
+    // Two borrows of the same context: C ∪ C = C and Coh(C, C) = 1
+    if std::ptr::eq(e1.context(), e2.context()) {
+        return OwnedEntity {
+            context: e1.context().clone(),
+            scale: e1.scale().intersect(e2.scale()),
+            perspective: e1.perspective().compose_with_threshold(
+                e2.perspective(),
+                1.0,
+                config.fusion_threshold,
+            ),
+            intensity: e1.intensity() + e2.intensity(),
+            history: composed_history(e1, e2, 1.0),
+        };
+    }
+
+    let coh = coherence_with(e1.context(), e2.context(), &config.coherence);
+    compose_at(e1, e2, coh, config.fusion_threshold)
+}
+
+/// Contextual Composition ⊕ with Coh(C₁, C₂) forced to `coh` instead of
+/// derived from the kernel. For what-if analysis only: the result need
+/// not correspond to any real pair of contexts.
+pub fn compose_with_coherence(
+    e1: &impl AsEntityRef,
+    e2: &impl AsEntityRef,
+    coh: f64,
+) -> OwnedEntity {
+    compose_with_coherence_config(e1, e2, coh, &SicConfig::default())
+}
+
+/// `compose_with_coherence` using the fusion threshold from `config`.
+pub fn compose_with_coherence_config(
+    e1: &impl AsEntityRef,
+    e2: &impl AsEntityRef,
+    coh: f64,
+    config: &SicConfig,
+) -> OwnedEntity {
+    compose_at(e1, e2, coh, config.fusion_threshold)
+}
 
+/// Contextual Composition ⊕ with an explicit context union: shared
+/// parameters merge by `strategy` (`Average` stays intensity-weighted)
+/// and the composed kind comes from the operand `kind` selects.
+pub fn compose_with_strategy(
+    e1: &impl AsEntityRef,
+    e2: &impl AsEntityRef,
+    strategy: MergeStrategy,
+    kind: KindInheritance,
+) -> OwnedEntity {
+    compose_with_strategy_config(e1, e2, strategy, kind, &SicConfig::default())
+}
+
+/// `compose_with_strategy` using the kernel and fusion threshold from
+/// `config`.
+pub fn compose_with_strategy_config(
+    e1: &impl AsEntityRef,
+    e2: &impl AsEntityRef,
+    strategy: MergeStrategy,
+    kind: KindInheritance,
+    config: &SicConfig,
+) -> OwnedEntity {
+    let coh = coherence_with(e1.context(), e2.context(), &config.coherence);
+    compose_merged(e1, e2, coh, config.fusion_threshold, strategy, kind)
+}
+
+fn compose_at(e1: &impl AsEntityRef, e2: &impl AsEntityRef, coh: f64, theta: f64) -> OwnedEntity {
+    compose_merged(e1, e2, coh, theta, MergeStrategy::Average, KindInheritance::Left)
+}
+
+fn compose_merged(
+    e1: &impl AsEntityRef,
+    e2: &impl AsEntityRef,
+    coh: f64,
+    theta: f64,
+    strategy: MergeStrategy,
+    kind: KindInheritance,
+) -> OwnedEntity {
     OwnedEntity {
-        context: e1.context.union(e2.context),
-        scale: e1.scale.intersect(&e2.scale),
-        perspective: e1.perspective.compose(&e2.perspective, coh),
-        intensity: e1.intensity + e2.intensity,
+        context: e1.context().merge(e2.context(), strategy, kind, e1.intensity(), e2.intensity()),
+        scale: e1.scale().intersect(e2.scale()),
+        perspective: e1.perspective().compose_with_threshold(e2.perspective(), coh, theta),
+        intensity: e1.intensity() + e2.intensity(),
+        history: composed_history(e1, e2, coh),
     }
 }
 
+/// Both operands' provenance followed by the ⊕ step.
+fn composed_history(
+    e1: &impl AsEntityRef,
+    e2: &impl AsEntityRef,
+    coh: f64,
+) -> Vec<TransformStep> {
+    let mut history = Vec::with_capacity(e1.history().len() + e2.history().len() + 1);
+    history.extend_from_slice(e1.history());
+    history.extend_from_slice(e2.history());
+    history.push(TransformStep {
+        operation: StepKind::Compose,
+        source: e1.context().kind.clone(),
+        target: e2.context().kind.clone(),
+        coherence: coh,
+    });
+    history
+}
+
+/// The entity's provenance followed by a transform step applying `coh`.
+fn transformed_history(
+    entity: &impl AsEntityRef,
+    target: &Context,
+    coh: f64,
+) -> Vec<TransformStep> {
+    let mut history = entity.history().to_vec();
+    history.push(TransformStep {
+        operation: StepKind::Transform,
+        source: entity.context().kind.clone(),
+        target: target.kind.clone(),
+        coherence: coh,
+    });
+    history
+}
+
+/// A composition chain fell below its cumulative coherence budget.
+#[derive(Debug, Clone)]
+pub struct BudgetExceeded {
+    /// The composition of the entities folded before the breach
+    pub partial: Box<OwnedEntity>,
+    /// How many entities `partial` contains
+    pub composed: usize,
+    /// Π Coh including the step that breached the budget
+    pub cumulative_coherence: f64,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cumulative coherence {} fell below budget after composing {} entities",
+            self.cumulative_coherence, self.composed
+        )
+    }
+}
+
+impl std::error::Error for BudgetExceeded {}
+
+/// Fold ⊕ over a chain, aborting once Π Coh drops below a budget.
+///
+/// Each step composes the running result with the next entity and
+/// multiplies in their context coherence. The step that would push the
+/// product below `min_cumulative_coh` is not applied; the error carries
+/// the composition so far. Like `compose_all`, an empty chain yields
+/// `None`.
+pub fn compose_all_budgeted<E: AsEntityRef>(
+    entities: &[E],
+    min_cumulative_coh: f64,
+) -> Result<Option<OwnedEntity>, BudgetExceeded> {
+    let Some((first, rest)) = entities.split_first() else {
+        return Ok(None);
+    };
+    let mut acc = to_owned_entity(first);
+    let mut cumulative = 1.0;
+    for (i, next) in rest.iter().enumerate() {
+        cumulative *= coherence(acc.context(), next.context());
+        if cumulative < min_cumulative_coh {
+            return Err(BudgetExceeded {
+                partial: Box::new(acc),
+                composed: i + 1,
+                cumulative_coherence: cumulative,
+            });
+        }
+        acc = compose(&acc, next);
+    }
+    Ok(Some(acc))
+}
+
+/// ⊕ over a whole collection — the monoid sum.
+///
+/// Folds left in slice order: ((E₁ ⊕ E₂) ⊕ E₃) ⊕ …. `None` for an empty
+/// slice; a single entity comes back as an owned copy. Intensities add
+/// up and the scale intersects down to the finest operand. Parameters
+/// present in every operand end up at their intensity-weighted mean, so
+/// they do not depend on the order; parameters missing from some
+/// operands, the context kind (from E₁) and perspective fusion do.
+pub fn compose_all<E: AsEntityRef>(entities: &[E]) -> Option<OwnedEntity> {
+    let (first, rest) = entities.split_first()?;
+    Some(rest.iter().fold(to_owned_entity(first), |acc, e| compose(&acc, e)))
+}
+
+fn to_owned_entity(entity: &impl AsEntityRef) -> OwnedEntity {
+    OwnedEntity {
+        context: entity.context().clone(),
+        scale: entity.scale().clone(),
+        perspective: entity.perspective().clone(),
+        intensity: entity.intensity(),
+        history: entity.history().to_vec(),
+    }
+}
+
+/// What a composition discarded — the irreversibility of ⊕ made visible.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompositionReport {
+    /// Coh(C₁, C₂) used for perspective fusion
+    pub context_coherence: f64,
+    /// True if ⊕_P fused the perspectives; false if both were kept as the
+    /// constituents of a `PerspectiveKind::Compound` (pooled when the
+    /// kinds match)
+    pub perspective_fused: bool,
+    /// The coarser scale discarded by S₁ ∩ S₂, if the scales differed
+    pub scale_dropped: Option<Scale>,
+    /// I₁ + I₂ of the operands
+    pub intensity_before: f64,
+    /// Intensity of the composed entity
+    pub intensity_after: f64,
+}
+
+/// Contextual Composition ⊕ with a report of the information lost.
+pub fn compose_with_report(
+    e1: &impl AsEntityRef,
+    e2: &impl AsEntityRef,
+) -> (OwnedEntity, CompositionReport) {
+    compose_with_report_config(e1, e2, &SicConfig::default())
+}
+
+/// `compose_with_report` using the kernel and fusion threshold from
+/// `config`.
+pub fn compose_with_report_config(
+    e1: &impl AsEntityRef,
+    e2: &impl AsEntityRef,
+    config: &SicConfig,
+) -> (OwnedEntity, CompositionReport) {
+    let composed = compose_config(e1, e2, config);
+    let context_coherence = coherence_with(e1.context(), e2.context(), &config.coherence);
+
+    let scale_dropped = if e1.scale() == e2.scale() {
+        None
+    } else if &composed.scale == e1.scale() {
+        Some(e2.scale().clone())
+    } else {
+        Some(e1.scale().clone())
+    };
+
+    let report = CompositionReport {
+        context_coherence,
+        perspective_fused: context_coherence > config.fusion_threshold,
+        scale_dropped,
+        intensity_before: e1.intensity() + e2.intensity(),
+        intensity_after: composed.intensity,
+    };
+    (composed, report)
+}
+
+/// Multi-scale Composition — ⊕ retaining every operand scale.
+///
+/// Identical to `compose` except the scale is the span S₁ ∪ S₂ (a
+/// `Scale::Multi` when the operands differ) instead of S₁ ∩ S₂, so the
+/// result remembers all the scales it was built from.
+pub fn compose_multiscale(e1: &impl AsEntityRef, e2: &impl AsEntityRef) -> OwnedEntity {
+    let mut composed = compose(e1, e2);
+    composed.scale = e1.scale().span(e2.scale());
+    composed
+}
+
 /// Scalar Modulation α × E (Section 6.2).
 ///
 /// α × E{C,S,P} = E{C,S,P, I:α}
-pub fn modulate<'a>(alpha: f64, entity: &Entity<'a>) -> Entity<'a> {
-    entity.modulate(alpha)
+///
+/// Takes `&Entity<'a>` (the result borrows the same context for `'a`) or
+/// `&'a OwnedEntity` (the result borrows the owned context).
+pub fn modulate<'a>(alpha: f64, entity: impl Into<Entity<'a>>) -> Entity<'a> {
+    entity.into().modulate(alpha)
 }
 
 /// Context Transformation T (Section 2.2).
 ///
 /// Transforms an entity from one context to another,
-/// with a coherence factor measuring information loss.
-pub fn transform(entity: &Entity, target_context: &Context) -> OwnedEntity {
-    let coh = coherence(entity.context, target_context);
+/// with a coherence factor measuring information loss. The step
+/// coherence also multiplies the entity's fidelity, so chained
+/// transforms accumulate their losses.
+pub fn transform(entity: &impl AsEntityRef, target_context: &Context) -> OwnedEntity {
+    let coh = coherence(entity.context(), target_context);
 
     OwnedEntity {
         context: target_context.clone(),
-        scale: entity.scale.clone(),
-        perspective: entity.perspective.clone(),
+        scale: entity.scale().clone(),
+        perspective: entity.perspective().clone(),
         // Intensity attenuated by coherence (information loss)
-        intensity: entity.intensity * coh,
+        intensity: entity.intensity() * coh,
+        history: transformed_history(entity, target_context, coh),
+    }
+}
+
+/// Transform every entity of a cluster into its representative context.
+///
+/// Returns the transformed entities and the mean information loss
+/// 1 − Coh(Cᵢ, C_rep) over the cluster (0 for an empty cluster) — low for
+/// a tight cluster, high for a loose one.
+pub fn collapse_cluster<E: AsEntityRef>(
+    entities: &[E],
+    representative: &Context,
+) -> (Vec<OwnedEntity>, f64) {
+    if entities.is_empty() {
+        return (Vec::new(), 0.0);
+    }
+    let loss: f64 = entities
+        .iter()
+        .map(|e| 1.0 - coherence(e.context(), representative))
+        .sum();
+    let collapsed = entities.iter().map(|e| transform(e, representative)).collect();
+    (collapsed, loss / entities.len() as f64)
+}
+
+/// Context Transformation T into a target context *and* perspective.
+///
+/// Intensity is attenuated by both the context and the perspective
+/// coherence: I' = I × Coh(C, C') × Coh(P, P').
+pub fn transform_to(
+    entity: &impl AsEntityRef,
+    target_context: &Context,
+    target_perspective: &Perspective,
+) -> OwnedEntity {
+    let context_coh = coherence(entity.context(), target_context);
+    let perspective_coh = perspective_coherence(entity.perspective(), target_perspective);
+
+    OwnedEntity {
+        context: target_context.clone(),
+        scale: entity.scale().clone(),
+        perspective: target_perspective.clone(),
+        intensity: entity.intensity() * context_coh * perspective_coh,
+        history: transformed_history(entity, target_context, context_coh * perspective_coh),
     }
 }
 
+/// Residual of a transformation — what T discarded.
+///
+/// An approximate decomposition, not an inverse: the residual carries the
+/// lost intensity I − I' and a context holding the parameters present in
+/// exactly one of C and C' (their symmetric difference), with the
+/// original's kind, scale and perspective.
+pub fn residual(original: &impl AsEntityRef, transformed: &impl AsEntityRef) -> OwnedEntity {
+    let (before, after) = (original.context(), transformed.context());
+    let mut context = Context::new(before.kind.clone());
+    for (name, &value) in &before.params {
+        if !after.params.contains_key(name) {
+            context.params.insert(name.clone(), value);
+        }
+    }
+    for (name, &value) in &after.params {
+        if !before.params.contains_key(name) {
+            context.params.insert(name.clone(), value);
+        }
+    }
+
+    OwnedEntity {
+        context,
+        scale: original.scale().clone(),
+        perspective: original.perspective().clone(),
+        intensity: original.intensity() - transformed.intensity(),
+        history: original.history().to_vec(),
+    }
+}
+
+/// Decomposition — the partial inverse of ⊕.
+///
+/// Given E₃ = E₁ ⊕ E₂ and one operand, recovers the other where the
+/// composition kept enough information:
+///   - intensity: I₂ = I₃ − I₁, which must be positive;
+///   - parameters: shared ones are un-averaged,
+///     θ₂ = ((I₁ + I₂)·θ₃ − I₁·θ₁) / I₂, and the rest belong to E₂;
+///   - scale: only a composite finer than the known scale, which is E₂'s own;
+///   - perspective: a fusion is un-weighted, w₂ = w₃ / Coh − w₁, while a
+///     compound has the known constituents removed.
+///
+/// Returns `None` when the loss is irreversible: E₂'s intensity or
+/// perspective weight was zero, a known parameter cannot be told
+/// apart from a shared one (θ₂ = θ₁), S₁ ∩ S₂ = S₁ (S₂ could be S₁ or
+/// any coarser scale), or the composite is inconsistent with the known
+/// operand, including a history that does not end in ⊕ or lacks the
+/// known operand's steps. One loss cannot be detected: the kinds of the
+/// composite (taken from the left operand) are assumed shared. E₂'s
+/// history, and with it its fidelity F₂ = F₃ / F₁, is what remains of the
+/// composite's once E₁'s steps and the ⊕ are removed.
+pub fn decompose(composed: &OwnedEntity, known: &impl AsEntityRef) -> Option<OwnedEntity> {
+    decompose_config(composed, known, &SicConfig::default())
+}
+
+/// `decompose` of a composite built by `compose_config` with `config`.
+pub fn decompose_config(
+    composed: &OwnedEntity,
+    known: &impl AsEntityRef,
+    config: &SicConfig,
+) -> Option<OwnedEntity> {
+    let (i_known, i_total) = (known.intensity(), composed.intensity);
+    let i_other = i_total - i_known;
+    if i_known < 0.0 || i_other <= 1e-12 * i_total.abs().max(1.0) {
+        return None;
+    }
+
+    let mut context = composed.context.clone();
+    for (key, theta_known) in known.context().canonical_params(&composed.context.aliases) {
+        let theta_total = context.params.get(&key).copied()?;
+        let theta_other = (i_total * theta_total - i_known * theta_known) / i_other;
+        if (theta_other - theta_known).abs() <= 1e-12 * theta_known.abs().max(1.0) {
+            return None;
+        }
+        context.params.insert(key, theta_other);
+    }
+
+    // S₃ = S₁ leaves S₂ anywhere from S₁ up, so only a finer S₃ is E₂'s
+    if composed.scale == *known.scale()
+        || known.scale().intersect(&composed.scale) != composed.scale
+    {
+        return None;
+    }
+    let scale = composed.scale.clone();
+
+    let coh = coherence_with(known.context(), &context, &config.coherence);
+    let known_perspective = known.perspective();
+    let perspective = if coh > config.fusion_threshold {
+        let weight = composed.perspective.weight / coh - known_perspective.weight;
+        if weight < 0.0 {
+            return None;
+        }
+        Perspective {
+            kind: composed.perspective.kind.clone(),
+            weight,
+        }
+    } else {
+        let mut parts = composed.perspective.constituents();
+        for (kind, weight) in known_perspective.constituents() {
+            if weight > 0.0 {
+                let (_, w) = parts.iter_mut().find(|(k, _)| *k == kind)?;
+                *w -= weight;
+            }
+        }
+        let tol = 1e-12 * composed.perspective.weight.abs().max(1.0);
+        if parts.iter().any(|(_, w)| *w < -tol) {
+            return None;
+        }
+        parts.retain(|(_, w)| *w > tol);
+        Perspective::from_constituents(parts)?
+    };
+
+    Some(OwnedEntity {
+        context,
+        scale,
+        perspective,
+        intensity: i_other,
+        history: other_history(&composed.history, known.history())?,
+    })
+}
+
+/// E₂'s steps within the history of E₁ ⊕ E₂: the final ⊕ and E₁'s steps,
+/// at whichever end of the operand steps they sit, removed. An untracked
+/// (empty) history stays empty.
+fn other_history(
+    composed: &[TransformStep],
+    known: &[TransformStep],
+) -> Option<Vec<TransformStep>> {
+    let Some((last, operands)) = composed.split_last() else {
+        return Some(Vec::new());
+    };
+    if last.operation != StepKind::Compose {
+        return None;
+    }
+    operands
+        .strip_prefix(known)
+        .or_else(|| operands.strip_suffix(known))
+        .map(<[TransformStep]>::to_vec)
+}
+
 /// Contextual Equivalence ≡{C} (Section 3.2).
 ///
 /// Two entities are contextually equivalent if they have the same
 /// context kind and their parameter distance is below a threshold.
-pub fn contextually_equivalent(e1: &Entity, e2: &Entity, threshold: f64) -> bool {
-    e1.context.kind == e2.context.kind && e1.context.distance(e2.context) < threshold
+/// See `structurally_equivalent` for the kind-blind variant.
+pub fn contextually_equivalent(
+    e1: &impl AsEntityRef,
+    e2: &impl AsEntityRef,
+    threshold: f64,
+) -> bool {
+    e1.context().kind == e2.context().kind && e1.context().distance(e2.context()) < threshold
+}
+
+/// Partition entities into classes under ≡{C}.
+///
+/// Distance thresholding is not transitive, so classes use single
+/// linkage: two entities share a class when a chain of pairwise
+/// `contextually_equivalent` entities connects them, even if the ends are
+/// farther apart than `threshold`. Every edge in a chain requires the
+/// same context kind, so different kinds never share a class.
+///
+/// Returns indices into `entities`, each class sorted ascending and the
+/// classes ordered by their smallest member.
+pub fn equivalence_classes<E: AsEntityRef>(entities: &[E], threshold: f64) -> Vec<Vec<usize>> {
+    // Union-find over the equivalence edges; labels follow smallest members
+    let mut clusterer = IncrementalClusterer::new(1.0);
+    for (i, entity) in entities.iter().enumerate() {
+        let edges: Vec<f64> = entities[..i]
+            .iter()
+            .map(|other| {
+                if contextually_equivalent(other, entity, threshold) {
+                    1.0
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        clusterer.insert(&edges);
+    }
+    cluster_groups(&clusterer.labels())
+}
+
+/// Structural equivalence — ≡{C} without the kind check.
+///
+/// Compares only the parameter vectors in the unified parameter space
+/// (missing parameters read as 0), so a `Custom("thermal")` context and a
+/// `Thermal` one with the same parameters are structurally equivalent
+/// even though they are not contextually equivalent.
+pub fn structurally_equivalent(
+    e1: &impl AsEntityRef,
+    e2: &impl AsEntityRef,
+    threshold: f64,
+) -> bool {
+    e1.context().distance(e2.context()) < threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{ContextKind, PerspectiveKind};
+
+    fn thermal() -> Context {
+        Context::with_params(ContextKind::Thermal, &[("temperature", 25.0)])
+    }
+
+    fn objective() -> Perspective {
+        Perspective::new(PerspectiveKind::Objective)
+    }
+
+    #[test]
+    fn modulate_keeps_the_context_lifetime() {
+        fn scaled<'c>(e: Entity<'c>) -> Entity<'c> {
+            modulate(2.0, &e)
+        }
+        let ctx = thermal();
+        let e = Entity::new(&ctx, Scale::Human, objective());
+        assert_eq!(scaled(e).intensity, 2.0);
+    }
+
+    #[test]
+    fn modulate_borrows_an_owned_entity() {
+        let owned = OwnedEntity::new(thermal(), Scale::Human, objective());
+        let m = modulate(0.5, &owned);
+        assert_eq!(m.intensity, 0.5);
+        assert!(core::ptr::eq(m.context, &owned.context));
+    }
+
+    #[test]
+    fn compose_config_uses_the_configured_kernel() {
+        let c1 = thermal();
+        let c2 = Context::with_params(ContextKind::Thermal, &[("temperature", 95.0)]);
+        let (e1, e2) = (
+            Entity::new(&c1, Scale::Human, objective()),
+            Entity::new(&c2, Scale::Human, objective()),
+        );
+        let config = SicConfig {
+            coherence: crate::coherence::CoherenceParams::new(
+                200.0,
+                crate::context::DistanceMetric::Chebyshev,
+            ),
+            ..SicConfig::default()
+        };
+        let composed = compose_config(&e1, &e2, &config);
+        let step = composed.history.last().unwrap();
+        assert_eq!(step.coherence, coherence_with(&c1, &c2, &config.coherence));
+        assert!(step.coherence > coherence(&c1, &c2));
+    }
+
+    #[test]
+    fn config_variants_use_the_fusion_threshold() {
+        let ctx = thermal();
+        let e1 = Entity::new(&ctx, Scale::Human, objective());
+        let e2 = Entity::new(&ctx, Scale::Human, Perspective::new(PerspectiveKind::Statistical));
+        let strict = SicConfig { fusion_threshold: 0.9, ..SicConfig::default() };
+        let fused = compose_with_coherence(&e1, &e2, 0.7);
+        let kept = compose_with_coherence_config(&e1, &e2, 0.7, &strict);
+        assert!(!matches!(fused.perspective.kind, PerspectiveKind::Compound(_)));
+        assert!(matches!(kept.perspective.kind, PerspectiveKind::Compound(_)));
+        let (_, report) = compose_with_report_config(&e1, &e2, &strict);
+        assert!(report.perspective_fused, "Coh(C, C) = 1 fuses under any θ < 1");
+    }
+
+    #[test]
+    fn compose_all_is_independent_of_order() {
+        let contexts: Vec<Context> = [(25.0, 1.0), (27.0, 1.2), (24.0, 0.9)]
+            .iter()
+            .map(|&(t, p)| {
+                Context::with_params(ContextKind::Thermal, &[("temperature", t), ("pressure", p)])
+            })
+            .collect();
+        let scales = [Scale::Human, Scale::Microscopic, Scale::Mesoscopic];
+        let entities: Vec<Entity> = contexts
+            .iter()
+            .zip(scales)
+            .zip([1.0, 2.0, 3.0])
+            .map(|((c, scale), i)| Entity::with_intensity(c, scale, objective(), i))
+            .collect();
+
+        let reference = compose_all(&entities).unwrap();
+        assert_eq!(reference.intensity, 6.0);
+        assert_eq!(reference.scale, Scale::Microscopic);
+        for order in [[0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]] {
+            let permuted: Vec<Entity> = order.iter().map(|&i| entities[i].clone()).collect();
+            let composed = compose_all(&permuted).unwrap();
+            assert_eq!(composed.intensity, reference.intensity);
+            assert_eq!(composed.scale, reference.scale);
+            assert_eq!(composed.perspective.kind, reference.perspective.kind);
+            for (key, value) in &reference.context.params {
+                assert!(close(composed.context.params[key], *value), "{key} in {order:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn empty_chains_compose_to_none() {
+        let none: [Entity; 0] = [];
+        assert!(compose_all(&none).is_none());
+        assert!(compose_all_budgeted(&none, 0.5).unwrap().is_none());
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-9 * a.abs().max(1.0)
+    }
+
+    #[test]
+    fn decompose_inverts_compose() {
+        let ctx = thermal();
+        let a = Entity::with_intensity(&ctx, Scale::Human, objective(), 2.0);
+        let mut b = OwnedEntity::new(
+            Context::with_params(ContextKind::Thermal, &[("temperature", 27.0), ("pressure", 1.1)]),
+            Scale::Microscopic,
+            objective(),
+        );
+        b.intensity = 3.0;
+
+        let back = decompose(&compose(&a, &b), &a).expect("compatible operands");
+        assert!(close(back.intensity, b.intensity));
+        assert_eq!(back.scale, b.scale);
+        assert_eq!(back.perspective.kind, b.perspective.kind);
+        assert!(close(back.perspective.weight, b.perspective.weight));
+        for (key, value) in &b.context.params {
+            assert!(close(back.context.params[key], *value), "{key}");
+        }
+    }
+
+    #[test]
+    fn fidelity_follows_the_history() {
+        let warm = thermal();
+        let hot = Context::with_params(ContextKind::Thermal, &[("temperature", 29.0)]);
+        let humid =
+            Context::with_params(ContextKind::Thermal, &[("temperature", 26.0), ("humidity", 0.4)]);
+        let a = transform(&transform(&Entity::new(&warm, Scale::Human, objective()), &hot), &warm);
+        let b = transform(&OwnedEntity::new(hot.clone(), Scale::Microscopic, objective()), &humid);
+        assert!(a.fidelity() < 1.0);
+        let ab = compose(&a, &b);
+        assert_eq!(ab.fidelity(), ab.total_attenuation());
+        assert!(close(ab.fidelity(), a.fidelity() * b.fidelity()));
+
+        let back = decompose(&ab, &a).expect("compatible operands");
+        assert_eq!(back.history, b.history);
+        assert_eq!(back.fidelity(), b.fidelity());
+        let front = decompose(&ab, &b);
+        assert!(front.is_none(), "a's scale is coarser than the composite's");
+    }
+
+    #[test]
+    fn decompose_rejects_an_ambiguous_scale() {
+        let ctx = thermal();
+        let a = Entity::with_intensity(&ctx, Scale::Human, objective(), 2.0);
+        let mut b = OwnedEntity::new(
+            Context::with_params(ContextKind::Thermal, &[("pressure", 1.1)]),
+            Scale::Cosmic,
+            objective(),
+        );
+        b.intensity = 3.0;
+        // Human ∩ Cosmic = Human: Cosmic is gone, so is an equal scale
+        assert!(decompose(&compose(&a, &b), &a).is_none());
+        b.scale = Scale::Human;
+        assert!(decompose(&compose(&a, &b), &a).is_none());
+    }
+
+    #[test]
+    fn transform_to_an_incompatible_perspective_attenuates() {
+        let ctx = thermal();
+        let e = Entity::new(&ctx, Scale::Human, objective());
+        let subjective = Perspective::new(PerspectiveKind::Subjective);
+        let moved = transform_to(&e, &ctx, &subjective);
+        assert_eq!(moved.intensity, 0.2);
+        assert_eq!(moved.fidelity(), 0.2);
+        assert_eq!(transform_to(&e, &ctx, &objective()).intensity, 1.0);
+    }
+
+    #[test]
+    fn multiscale_compose_keeps_both_scales() {
+        let quantum = Context::with_params(ContextKind::Quantum, &[("energy", 25.0)]);
+        let (ctx, p) = (thermal(), objective());
+        let human = Entity::new(&ctx, Scale::Human, p.clone());
+        let atom = Entity::new(&quantum, Scale::Quantum, p);
+        let composed = compose_multiscale(&human, &atom);
+        assert_eq!(composed.scale, Scale::Multi(vec![Scale::Quantum, Scale::Human]));
+        assert_eq!(composed.scale.finest(), &Scale::Quantum);
+        assert_eq!(composed.scale.coarsest(), &Scale::Human);
+        assert_eq!(compose(&human, &atom).scale, Scale::Quantum);
+    }
+
+    #[test]
+    fn report_flags_unfused_perspectives() {
+        let far = Context::with_params(ContextKind::Thermal, &[("temperature", 900.0)]);
+        let (near, p) = (thermal(), objective());
+        let e1 = Entity::new(&near, Scale::Human, p.clone());
+        let subjective = Perspective::new(PerspectiveKind::Subjective);
+        let e2 = Entity::new(&far, Scale::Microscopic, subjective);
+        let (composed, report) = compose_with_report(&e1, &e2);
+        assert!(report.context_coherence < SicConfig::default().fusion_threshold);
+        assert!(!report.perspective_fused);
+        assert!(matches!(composed.perspective.kind, PerspectiveKind::Compound(_)));
+        assert_eq!(report.scale_dropped, Some(Scale::Human));
+        assert_eq!(report.intensity_before, 2.0);
+
+        let twin = Entity::new(&near, Scale::Human, p);
+        let (_, report) = compose_with_report(&e1, &twin);
+        assert!(report.perspective_fused);
+        assert_eq!(report.scale_dropped, None);
+    }
+
+    #[test]
+    fn compose_takes_owned_entities_directly() {
+        let warm = Context::with_params(ContextKind::Thermal, &[("temperature", 27.0)]);
+        let a = OwnedEntity::new(thermal(), Scale::Human, objective());
+        let b = OwnedEntity::new(warm, Scale::Mesoscopic, objective());
+        let owned = compose(&a, &b);
+
+        let borrowed = compose(&a.as_entity(), &b.as_entity());
+        assert_eq!(owned.context.params, borrowed.context.params);
+        assert_eq!(owned.scale, Scale::Mesoscopic);
+        assert_eq!(owned.intensity, 2.0);
+        // the operands are still usable afterwards
+        assert_eq!(compose(&owned, &a).intensity, 3.0);
+    }
+
+    #[test]
+    fn coherence_override_decides_perspective_fusion() {
+        let far = Context::with_params(ContextKind::Quantum, &[("energy", 3.2)]);
+        let near = thermal();
+        let e1 = Entity::new(&near, Scale::Human, objective());
+        let e2 = Entity::new(&far, Scale::Human, Perspective::new(PerspectiveKind::Subjective));
+
+        let fused = compose_with_coherence(&e1, &e2, 0.9).perspective;
+        assert_eq!(fused.kind, PerspectiveKind::Objective);
+        assert!((fused.weight - 1.8).abs() < 1e-12);
+
+        let coexisting = compose_with_coherence(&e1, &e2, 0.1).perspective;
+        let PerspectiveKind::Compound(parts) = &coexisting.kind else {
+            panic!("expected a compound, got {:?}", coexisting.kind);
+        };
+        assert_eq!(parts.len(), 2);
+        assert_eq!(coexisting.weight, 2.0);
+    }
+
+    #[test]
+    fn residual_carries_the_lost_intensity() {
+        let ctx =
+            Context::with_params(ContextKind::Thermal, &[("temperature", 25.0), ("humidity", 0.4)]);
+        let target =
+            Context::with_params(ContextKind::Thermal, &[("temperature", 28.0), ("pressure", 1.0)]);
+        let original = Entity::new(&ctx, Scale::Human, objective()).modulate(4.0);
+        let moved = transform(&original, &target);
+        let lost = residual(&original, &moved);
+
+        assert!(moved.intensity < original.intensity);
+        assert_eq!(lost.intensity, original.intensity - moved.intensity);
+        let mut names: Vec<&str> = lost.context.params.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["humidity", "pressure"]);
+    }
+
+    #[test]
+    fn composing_on_a_shared_context_keeps_it_exactly() {
+        let mut ctx = thermal();
+        ctx.params.insert("humidity".to_string(), 0.4);
+        ctx.set_unit("temperature", "C");
+        let e1 = Entity::new(&ctx, Scale::Human, objective());
+        let e2 = Entity::new(&ctx, Scale::Microscopic, objective()).modulate(3.0);
+        let composed = compose(&e1, &e2);
+        assert_eq!(composed.context.params, ctx.params);
+        assert_eq!(composed.context.unit("temperature"), Some("C"));
+        assert_eq!(composed.scale, Scale::Microscopic);
+        assert_eq!(composed.intensity, 4.0);
+        // Coh(C, C) = 1 fuses the perspectives at full weight
+        assert_eq!(composed.perspective.weight, 2.0);
+    }
+
+    #[test]
+    fn same_params_across_kinds_are_only_structurally_equivalent() {
+        let kind = ContextKind::Custom("thermal".into());
+        let custom = Context::with_params(kind, &[("temperature", 25.0)]);
+        let builtin = thermal();
+        let e1 = Entity::new(&custom, Scale::Human, objective());
+        let e2 = Entity::new(&builtin, Scale::Human, objective());
+        assert!(structurally_equivalent(&e1, &e2, 1e-9));
+        assert!(!contextually_equivalent(&e1, &e2, 1e-9));
+        assert_eq!(equivalence_classes(&[e1, e2], 1e-9), vec![vec![0], vec![1]]);
+    }
+
+    #[test]
+    fn the_more_intense_operand_dominates_the_context() {
+        let cold = Context::with_params(ContextKind::Thermal, &[("temperature", 20.0)]);
+        let hot = Context::with_params(ContextKind::Thermal, &[("temperature", 40.0)]);
+        let weak = Entity::new(&cold, Scale::Human, objective());
+        let strong = Entity::new(&hot, Scale::Human, objective()).modulate(3.0);
+        // (1·20 + 3·40) / 4
+        assert_eq!(compose(&weak, &strong).context.param("temperature"), 35.0);
+        assert_eq!(compose(&strong, &weak).context.param("temperature"), 35.0);
+        assert_eq!(cold.union_weighted(&hot, 1.0, 3.0).param("temperature"), 35.0);
+    }
+
+    #[test]
+    fn tight_cluster_collapses_with_less_loss_than_a_loose_one() {
+        let temps = |ts: &[f64]| -> Vec<Context> {
+            ts.iter()
+                .map(|&t| Context::with_params(ContextKind::Thermal, &[("temperature", t)]))
+                .collect()
+        };
+        let tight = temps(&[24.0, 25.0, 26.0]);
+        let loose = temps(&[5.0, 25.0, 60.0]);
+        let entities = |ctxs: &[Context]| -> Vec<OwnedEntity> {
+            ctxs.iter().map(|c| OwnedEntity::new(c.clone(), Scale::Human, objective())).collect()
+        };
+        let representative = thermal();
+
+        let (collapsed, tight_loss) = collapse_cluster(&entities(&tight), &representative);
+        assert_eq!(collapsed.len(), 3);
+        assert!(collapsed.iter().all(|e| e.context.params == representative.params));
+        let (_, loose_loss) = collapse_cluster(&entities(&loose), &representative);
+
+        assert!(tight_loss < 0.05, "tight loss {tight_loss}");
+        assert!(loose_loss > 0.3, "loose loss {loose_loss}");
+        assert_eq!(collapse_cluster::<OwnedEntity>(&[], &representative).1, 0.0);
+    }
+
+    #[test]
+    fn two_transforms_multiply_fidelity_past_the_threshold() {
+        let start = thermal();
+        let warmer = Context::with_params(ContextKind::Thermal, &[("temperature", 29.0)]);
+        let hotter = Context::with_params(ContextKind::Thermal, &[("temperature", 33.0)]);
+        let fresh = OwnedEntity::new(start.clone(), Scale::Human, objective());
+        assert_eq!(fresh.fidelity(), 1.0);
+
+        let once = transform(&fresh, &warmer);
+        let twice = transform(&once, &hotter);
+        let first = coherence(&start, &warmer);
+        let second = coherence(&warmer, &hotter);
+        assert!(close(once.fidelity(), first));
+        assert!(close(twice.fidelity(), first * second));
+
+        // one step stays above the threshold, the second crosses it
+        let threshold = (first + first * second) / 2.0;
+        assert!(!once.is_degraded(threshold));
+        assert!(twice.is_degraded(threshold));
+    }
+
+    #[test]
+    fn low_coherence_mid_chain_breaks_the_budget() {
+        let warm = thermal();
+        let near = Context::with_params(ContextKind::Thermal, &[("temperature", 25.5)]);
+        let far = Context::with_params(ContextKind::Quantum, &[("energy", 3.2)]);
+        let e = |ctx| Entity::new(ctx, Scale::Human, objective());
+
+        let good = [e(&warm), e(&near), e(&warm)];
+        let whole = compose_all_budgeted(&good, 0.5).unwrap().unwrap();
+        assert_eq!(whole.intensity, 3.0);
+
+        let chain = [e(&warm), e(&near), e(&far), e(&warm)];
+        let err = compose_all_budgeted(&chain, 0.5).unwrap_err();
+        assert_eq!(err.composed, 2, "stops before the quantum entity");
+        assert_eq!(err.partial.intensity, 2.0);
+        assert!(err.cumulative_coherence < 0.5);
+        assert!(compose_all_budgeted::<Entity>(&[], 0.5).unwrap().is_none());
+    }
+
+    #[test]
+    fn composing_ev_with_joules_converts_before_averaging() {
+        const EV: f64 = 1.602_176_634e-19;
+        let mut in_ev = Context::with_params(ContextKind::Quantum, &[("energy", 1.0)]);
+        in_ev.set_unit("energy", "eV");
+        let mut in_joules = Context::with_params(ContextKind::Quantum, &[("energy", 3.0 * EV)]);
+        in_joules.set_unit("energy", "J");
+
+        let a = Entity::new(&in_ev, Scale::Microscopic, objective());
+        let b = Entity::new(&in_joules, Scale::Microscopic, objective());
+        let fused = compose(&a, &b);
+        let energy = fused.context.params["energy"];
+        assert!((energy / EV - 2.0).abs() < 1e-12, "got {energy} J, not 2 eV");
+        assert_eq!(fused.context.unit("energy"), Some("J"));
+
+        // the same energy in either unit is the same context
+        let mut same = in_joules.clone();
+        same.params.insert("energy".to_string(), EV);
+        assert!(in_ev.distance(&same) < 1e-12);
+        assert!((coherence(&in_ev, &same) - 1.0).abs() < 1e-12);
+    }
 }
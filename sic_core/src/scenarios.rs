@@ -0,0 +1,61 @@
+//! Canonical sensor scenarios — reproducible input sequences
+//!
+//! Named signal shapes for exercising the nested learning system in tests
+//! and examples. Every generator is deterministic: the same arguments
+//! (and seed, for noise) always produce the same sequence.
+
+use crate::coherence::SplitMix64;
+use std::f64::consts::TAU;
+
+/// `low` for samples before `at`, `high` from `at` on.
+pub fn step(n: usize, at: usize, low: f64, high: f64) -> Vec<f64> {
+    (0..n).map(|i| if i < at { low } else { high }).collect()
+}
+
+/// Linear ramp from `start` (first sample) to `end` (last sample).
+pub fn ramp(n: usize, start: f64, end: f64) -> Vec<f64> {
+    if n == 1 {
+        return vec![start];
+    }
+    (0..n)
+        .map(|i| start + (end - start) * i as f64 / (n - 1) as f64)
+        .collect()
+}
+
+/// amp · sin(2π·freq·i), with `freq` in cycles per sample.
+pub fn sine(freq: f64, amp: f64, n: usize) -> Vec<f64> {
+    (0..n).map(|i| amp * (TAU * freq * i as f64).sin()).collect()
+}
+
+/// Uniform noise in [−amp, amp) from a seeded SplitMix64 stream.
+pub fn noise(n: usize, amp: f64, seed: u64) -> Vec<f64> {
+    let mut rng = SplitMix64(seed);
+    (0..n)
+        .map(|_| {
+            // 53 random bits → uniform in [0, 1)
+            let unit = (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+            amp * (2.0 * unit - 1.0)
+        })
+        .collect()
+}
+
+/// Zero everywhere except `amp` at sample `at`.
+pub fn impulse(n: usize, at: usize, amp: f64) -> Vec<f64> {
+    (0..n).map(|i| if i == at { amp } else { 0.0 }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sine_is_reproducible() {
+        let a = sine(0.05, 300.0, 64);
+        assert_eq!(a, sine(0.05, 300.0, 64));
+        assert_eq!(a.len(), 64);
+        assert_eq!(a[0], 0.0);
+        assert!((a[5] - 300.0).abs() < 1e-9, "a quarter period in");
+        assert_ne!(a, sine(0.05, 300.0 + 1e-9, 64));
+        assert_eq!(noise(32, 1.0, 7), noise(32, 1.0, 7));
+    }
+}
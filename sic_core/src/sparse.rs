@@ -0,0 +1,262 @@
+//! Sparse Coherence Matrix — Section 11 at large N
+//!
+//! Compressed-row storage of the off-diagonal entries of 𝕄. The diagonal
+//! is implicitly 1 (Axiom 4) and never stored, so a clustered or
+//! friction-thinned matrix costs memory proportional to its edges.
+
+use crate::coherence::{
+    coherence_with, power_iterate, settled, Coherence, CoherenceMatrix, CoherenceParams,
+};
+use crate::context::Context;
+
+/// 𝕄 in compressed-row form: row i's entries are
+/// `columns[row_offsets[i]..row_offsets[i + 1]]` with matching `values`,
+/// sorted by column. 𝕄ᵢᵢ = 1 is implicit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseCoherenceMatrix {
+    pub n: usize,
+    pub row_offsets: Vec<usize>,
+    pub columns: Vec<usize>,
+    pub values: Vec<f64>,
+}
+
+impl SparseCoherenceMatrix {
+    /// Build from per-row adjacency lists of (column, 𝕄ᵢⱼ).
+    ///
+    /// Diagonal entries are dropped, and duplicate columns within a row
+    /// keep the last value. Symmetry is the caller's responsibility.
+    pub fn from_adjacency(rows: Vec<Vec<(usize, f64)>>) -> Self {
+        let n = rows.len();
+        let mut row_offsets = Vec::with_capacity(n + 1);
+        let mut columns = Vec::new();
+        let mut values = Vec::new();
+        row_offsets.push(0);
+        for (i, mut row) in rows.into_iter().enumerate() {
+            row.retain(|&(j, _)| j != i);
+            row.sort_by_key(|&(j, _)| j);
+            for (k, &(j, v)) in row.iter().enumerate() {
+                if row.get(k + 1).is_some_and(|&(next, _)| next == j) {
+                    continue;
+                }
+                columns.push(j);
+                values.push(v);
+            }
+            row_offsets.push(columns.len());
+        }
+        Self {
+            n,
+            row_offsets,
+            columns,
+            values,
+        }
+    }
+
+    /// Build 𝕄 with friction applied inline: only pairs with
+    /// Coh ≥ ε (and > 0) are ever stored, so no dense intermediate exists.
+    pub fn from_contexts_sparse(contexts: &[Context], epsilon: impl Into<Coherence>) -> Self {
+        Self::from_contexts_sparse_with(contexts, epsilon, &CoherenceParams::default())
+    }
+
+    /// `from_contexts_sparse` under explicit kernel parameters, keeping
+    /// the entries of `CoherenceMatrix::from_contexts_with` that pass ε.
+    pub fn from_contexts_sparse_with(
+        contexts: &[Context],
+        epsilon: impl Into<Coherence>,
+        params: &CoherenceParams,
+    ) -> Self {
+        let epsilon = epsilon.into().value();
+        let n = contexts.len();
+        let mut rows: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let coh = coherence_with(&contexts[i], &contexts[j], params);
+                if coh > 0.0 && coh >= epsilon {
+                    rows[i].push((j, coh));
+                    rows[j].push((i, coh)); // Axiom 5: symmetry
+                }
+            }
+        }
+        Self::from_adjacency(rows)
+    }
+
+    /// Number of stored off-diagonal entries.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Stored neighbours of context i as (j, 𝕄ᵢⱼ).
+    pub fn neighbors(&self, i: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let range = self.row_offsets[i]..self.row_offsets[i + 1];
+        self.columns[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.values[range].iter().copied())
+    }
+
+    /// Number of stored edges of context i.
+    pub fn degree(&self, i: usize) -> usize {
+        self.row_offsets[i + 1] - self.row_offsets[i]
+    }
+
+    /// 𝕄ᵢⱼ, with 1 on the diagonal and 0 for unstored entries.
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        if i == j {
+            return 1.0;
+        }
+        let range = self.row_offsets[i]..self.row_offsets[i + 1];
+        match self.columns[range.clone()].binary_search(&j) {
+            Ok(k) => self.values[range.start + k],
+            Err(_) => 0.0,
+        }
+    }
+
+    /// Connected clusters over stored edges with 𝕄ᵢⱼ > 0 (Section 11.4).
+    ///
+    /// Clusters are numbered in ascending order of their smallest member,
+    /// matching `CoherenceMatrix::find_clusters`.
+    pub fn find_clusters(&self) -> Vec<usize> {
+        let mut labels = vec![usize::MAX; self.n];
+        let mut current_cluster = 0;
+
+        for start in 0..self.n {
+            if labels[start] != usize::MAX {
+                continue;
+            }
+            let mut queue = vec![start];
+            labels[start] = current_cluster;
+
+            while let Some(node) = queue.pop() {
+                for (neighbor, w) in self.neighbors(node) {
+                    if labels[neighbor] == usize::MAX && w > 0.0 {
+                        labels[neighbor] = current_cluster;
+                        queue.push(neighbor);
+                    }
+                }
+            }
+            current_cluster += 1;
+        }
+
+        labels
+    }
+
+    /// Number of connected clusters.
+    pub fn num_clusters(&self) -> usize {
+        let labels = self.find_clusters();
+        labels.iter().copied().max().map(|m| m + 1).unwrap_or(0)
+    }
+
+    /// 𝕄v over the stored entries plus the implicit unit diagonal.
+    fn multiply(&self, v: &[f64]) -> Vec<f64> {
+        (0..self.n)
+            .map(|i| v[i] + self.neighbors(i).map(|(j, w)| w * v[j]).sum::<f64>())
+            .collect()
+    }
+
+    /// Global coherence Γ = λ_max / N (Section 11.6).
+    ///
+    /// Power iteration with the stopping rule and budget of
+    /// `CoherenceMatrix::global_coherence` (`tol` 1e-12, 1000 iterations),
+    /// touching only stored entries.
+    pub fn global_coherence(&self) -> f64 {
+        if self.n == 0 {
+            return 0.0;
+        }
+        let run = power_iterate(self.n, 1000, |v| self.multiply(v), settled(1e-12), |_, _| {});
+        run.lambda / self.n as f64
+    }
+
+    /// Local collapse of a cluster (Section 11.5): (γ_k, γ_k > θ) with
+    /// γ_k = λ₁⁽ᵏ⁾ / Tr(𝕄ₖ) and Tr(𝕄ₖ) = Nₖ from the unit diagonal. Same
+    /// stopping rule and budget as `global_coherence`.
+    pub fn local_collapse(
+        &self,
+        cluster_indices: &[usize],
+        theta: impl Into<Coherence>,
+    ) -> (f64, bool) {
+        let theta = theta.into().value();
+        let n_k = cluster_indices.len();
+        if n_k <= 1 {
+            return (1.0, true);
+        }
+
+        // Global index → position within the cluster
+        let mut local = vec![usize::MAX; self.n];
+        for (si, &i) in cluster_indices.iter().enumerate() {
+            local[i] = si;
+        }
+
+        let multiply = |v: &[f64]| -> Vec<f64> {
+            cluster_indices
+                .iter()
+                .enumerate()
+                .map(|(si, &i)| {
+                    let off: f64 = self
+                        .neighbors(i)
+                        .filter(|&(j, _)| local[j] != usize::MAX)
+                        .map(|(j, weight)| weight * v[local[j]])
+                        .sum();
+                    v[si] + off
+                })
+                .collect()
+        };
+        let lambda = power_iterate(n_k, 1000, multiply, settled(1e-12), |_, _| {}).lambda;
+
+        let gamma_k = lambda / n_k as f64;
+        (gamma_k, gamma_k > theta)
+    }
+}
+
+impl CoherenceMatrix {
+    /// Compressed-row copy of 𝕄, keeping nonzero off-diagonal entries.
+    pub fn to_sparse(&self) -> SparseCoherenceMatrix {
+        let rows = self
+            .data
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|&(j, &v)| j != i && v != 0.0)
+                    .map(|(j, &v)| (j, v))
+                    .collect()
+            })
+            .collect();
+        SparseCoherenceMatrix::from_adjacency(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::coherence::CoherenceMatrix;
+    use crate::context::{Context, ContextKind};
+
+    fn contexts() -> Vec<Context> {
+        [20.0, 20.5, 21.0, 80.0, 80.4]
+            .iter()
+            .map(|&t| Context::with_params(ContextKind::Thermal, &[("temperature", t)]))
+            .collect()
+    }
+
+    #[test]
+    fn sparse_matches_dense() {
+        let mut dense = CoherenceMatrix::from_contexts(&contexts());
+        dense.apply_friction(0.1);
+        let sparse = dense.to_sparse();
+        assert_eq!(sparse.find_clusters(), dense.find_clusters());
+        assert_eq!(sparse.num_clusters(), 2);
+        assert!((sparse.global_coherence() - dense.global_coherence()).abs() < 1e-12);
+        for cluster in [&[0, 1, 2][..], &[3, 4]] {
+            let (dense_k, _) = dense.local_collapse(cluster, 0.5);
+            let (sparse_k, _) = sparse.local_collapse(cluster, 0.5);
+            assert!((dense_k - sparse_k).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn sparse_builder_skips_the_dense_intermediate() {
+        let mut dense = CoherenceMatrix::from_contexts(&contexts());
+        dense.apply_friction(0.1);
+        let built = crate::sparse::SparseCoherenceMatrix::from_contexts_sparse(&contexts(), 0.1);
+        assert_eq!(built, dense.to_sparse());
+    }
+}
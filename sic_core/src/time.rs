@@ -0,0 +1,53 @@
+//! Timestamps for events, decay and the nested learning clocks
+//!
+//! With `std`, `Instant` is `std::time::Instant`. Without it there is no
+//! clock at all: `Instant` is a millisecond timestamp the caller builds
+//! from its own tick source (e.g. Arduino `millis()`) and passes to the
+//! `*_at` functions. Each system keeps its own time; nothing is global.
+
+#[cfg(feature = "std")]
+pub use std::time::Instant;
+
+#[cfg(not(feature = "std"))]
+pub use self::millis::Instant;
+
+#[cfg(not(feature = "std"))]
+mod millis {
+    use core::ops::{Add, AddAssign};
+    use core::time::Duration;
+
+    /// A caller-supplied monotonic timestamp in milliseconds.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct Instant(u64);
+
+    impl Instant {
+        pub const fn from_millis(ms: u64) -> Self {
+            Self(ms)
+        }
+
+        pub const fn as_millis(&self) -> u64 {
+            self.0
+        }
+
+        /// Time since `earlier`, or zero if `earlier` is later.
+        pub fn saturating_duration_since(&self, earlier: Instant) -> Duration {
+            Duration::from_millis(self.0.saturating_sub(earlier.0))
+        }
+    }
+
+    impl Add<Duration> for Instant {
+        type Output = Instant;
+
+        /// Sub-millisecond remainders are dropped.
+        fn add(self, rhs: Duration) -> Instant {
+            let ms = u64::try_from(rhs.as_millis()).unwrap_or(u64::MAX);
+            Instant(self.0.saturating_add(ms))
+        }
+    }
+
+    impl AddAssign<Duration> for Instant {
+        fn add_assign(&mut self, rhs: Duration) {
+            *self = *self + rhs;
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Parameter units — convert before combining
+//!
+//! Two contexts may both carry "energy", one in eV and one in J. Averaging
+//! or differencing the raw numbers is physically meaningless, so a
+//! parameter annotated with a unit is converted to its canonical unit
+//! before `distance`, `coherence` and `union` combine it.
+
+use crate::context::Context;
+use crate::Map;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Conversion table: unit → (canonical unit, factor), with
+/// value_canonical = value × factor. Only multiplicative units are
+/// supported (no affine scales such as °C).
+#[derive(Debug, Clone, Default)]
+pub struct UnitRegistry {
+    units: Map<String, (String, f64)>,
+}
+
+impl UnitRegistry {
+    /// An empty registry — every unit is left as is.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Common SI conversions for energy (J), length (m), time (s) and
+    /// mass (kg). Used by `Context` when parameters carry units.
+    pub fn standard() -> Self {
+        let mut registry = Self::new();
+        for (unit, canonical, factor) in [
+            ("J", "J", 1.0),
+            ("kJ", "J", 1e3),
+            ("eV", "J", 1.602_176_634e-19),
+            ("keV", "J", 1.602_176_634e-16),
+            ("MeV", "J", 1.602_176_634e-13),
+            ("cal", "J", 4.184),
+            ("kcal", "J", 4184.0),
+            ("m", "m", 1.0),
+            ("km", "m", 1e3),
+            ("cm", "m", 1e-2),
+            ("mm", "m", 1e-3),
+            ("nm", "m", 1e-9),
+            ("s", "s", 1.0),
+            ("ms", "s", 1e-3),
+            ("min", "s", 60.0),
+            ("h", "s", 3600.0),
+            ("kg", "kg", 1.0),
+            ("g", "kg", 1e-3),
+        ] {
+            registry.register(unit, canonical, factor);
+        }
+        registry
+    }
+
+    /// Declare `unit` = `factor` × `canonical`.
+    pub fn register(&mut self, unit: &str, canonical: &str, factor: f64) {
+        self.units
+            .insert(unit.to_string(), (canonical.to_string(), factor));
+    }
+
+    /// `value` in `unit` expressed in its canonical unit, or `None` if the
+    /// unit is not registered.
+    pub fn to_canonical(&self, unit: &str, value: f64) -> Option<(&str, f64)> {
+        self.units
+            .get(unit)
+            .map(|(canonical, factor)| (canonical.as_str(), value * factor))
+    }
+
+    /// Copy of `context` with every registered unit converted to canonical.
+    pub fn canonicalize(&self, context: &Context) -> Context {
+        let mut converted = context.clone();
+        for (name, unit) in &context.units {
+            if let (Some(&value), Some((canonical, factor))) =
+                (context.params.get(name), self.units.get(unit))
+            {
+                converted.params.insert(name.clone(), value * factor);
+                converted.units.insert(name.clone(), canonical.clone());
+            }
+        }
+        converted
+    }
+}